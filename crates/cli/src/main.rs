@@ -1,17 +1,30 @@
 use clap::{Parser, Subcommand};
 use core::{
-    HardwareWalletType, approve_hash, exec_transaction, generate_admin_actions_from_json,
-    generate_root_update_txs,
-    types::simulation_config::SimulationConfig,
-    utils::simulate::{
-        simulate_admin_tx_and_generate_safe_hash,
-        simulate_timelock_admin_txs_and_generate_safe_hashes,
+    HardwareWalletType, approve_hash, decode_calldata, exec_transaction, export_permission_matrix,
+    generate_admin_batches_from_json, generate_root_update_txs, has_any_role,
+    report::{
+        model::{RootUpdate, TransactionReport},
+        writer::{ReportFormat, ReportWriter},
+    },
+    simulate_owner_rotation_post_check,
+    types::{
+        admin_batch::AdminBatch, config_editor::ConfigEditor, config_wrapper::ConfigWrapper,
+        simulation_config::SimulationConfig,
+    },
+    utils::{
+        discover::discover_product,
+        probe::probe_contract_versions,
+        simulate::{
+            simulate_admin_tx_and_generate_safe_hash, simulate_admin_txs_in_sequence,
+            simulate_timelock_admin_txs_and_generate_safe_hashes,
+        },
     },
 };
 use eyre::{Result, eyre};
-use serde_json::Value;
+use serde_json::{Value, json};
 use std::fs;
 use std::path::Path;
+use tokio::task::JoinSet;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -124,6 +137,295 @@ enum Commands {
         #[arg(long = "ledger", short = 'l', conflicts_with = "trezor")]
         ledger: bool,
     },
+    /// Move every contract of a product to a new owner, and optionally a new authority
+    RotateOwner {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// New owner, usually the new Safe
+        #[arg(long = "new-owner")]
+        new_owner: String,
+
+        /// New authority for contracts that have one
+        #[arg(long = "new-authority")]
+        new_authority: Option<String>,
+
+        /// Nonce, fetched from the multisig if not set
+        #[arg(long = "nonce")]
+        nonce: Option<u32>,
+
+        /// Set the product multisig to the new owner in config.toml once the txs are generated
+        #[arg(long = "update-config")]
+        update_config: bool,
+
+        /// Rotate the other contracts when some are not owned by the current admin
+        #[arg(long = "skip-foreign-owned")]
+        skip_foreign_owned: bool,
+
+        /// Use Trezor hardware wallet
+        #[arg(long = "trezor", short = 't', conflicts_with = "ledger")]
+        trezor: bool,
+
+        /// Use Ledger hardware wallet
+        #[arg(long = "ledger", short = 'l', conflicts_with = "trezor")]
+        ledger: bool,
+    },
+    /// Tear down a solver and set up its replacement on every product with a queue
+    RotateSolver {
+        /// Solver contract to tear down
+        #[arg(long = "old-solver")]
+        old_solver: String,
+
+        /// Solver contract to set up
+        #[arg(long = "new-solver")]
+        new_solver: String,
+
+        /// Make self solve functions of the new solver public
+        #[arg(long = "allow-self-solves")]
+        allow_self_solves: bool,
+
+        /// Solver template of the new solver, the product default if not set
+        #[arg(long = "template")]
+        template: Option<String>,
+
+        /// Solver template the old solver was set up with, the product default if not set
+        #[arg(long = "old-template")]
+        old_template: Option<String>,
+
+        /// Networks to rotate the solver on, every network with a queue if not set
+        #[arg(long = "network", short = 'n')]
+        network_ids: Vec<u32>,
+
+        /// Use Trezor hardware wallet
+        #[arg(long = "trezor", short = 't', conflicts_with = "ledger")]
+        trezor: bool,
+
+        /// Use Ledger hardware wallet
+        #[arg(long = "ledger", short = 'l', conflicts_with = "trezor")]
+        ledger: bool,
+    },
+    /// Onboard or offboard a strategist, keeping the strategists in config.toml in sync
+    Strategist {
+        #[command(subcommand)]
+        command: StrategistCommands,
+    },
+    /// Edit config.toml, keeping its formatting and comments
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Export who can call what on a product, rebuilt from its RolesAuthority events
+    Permissions {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// Output format, one of json, csv, markdown or dot
+        #[arg(long = "format", short = 'f', default_value = "markdown")]
+        format: String,
+
+        /// First block scanned for RolesAuthority events
+        #[arg(long = "from-block", default_value_t = 0)]
+        from_block: u64,
+
+        /// File to write the export to, printed if not set
+        #[arg(long = "output", short = 'o')]
+        output: Option<String>,
+    },
+    /// Decode calldata into the called function and its arguments
+    Decode {
+        /// Hex encoded calldata
+        #[arg(long = "data", short = 'd')]
+        data: String,
+    },
+    /// Incident response commands
+    Emergency {
+        #[command(subcommand)]
+        command: EmergencyCommands,
+    },
+    /// Detect product contract interface versions and record them in config.toml
+    ProbeVersions {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// Print the detected versions without writing them to config.toml
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum StrategistCommands {
+    /// Grant roles and the current product root to a strategist, and add it to config.toml
+    Add {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// Strategist address
+        #[arg(long = "strategist", short = 's')]
+        strategist: String,
+
+        /// Roles to grant, by name or id
+        #[arg(long = "role", required = true)]
+        roles: Vec<String>,
+
+        /// Merkle root to set, the root of the other strategists if not set
+        #[arg(long = "root", default_value = "current")]
+        root: String,
+
+        /// Nonce, fetched from the multisig if not set
+        #[arg(long = "nonce")]
+        nonce: Option<u32>,
+
+        /// Use Trezor hardware wallet
+        #[arg(long = "trezor", short = 't', conflicts_with = "ledger")]
+        trezor: bool,
+
+        /// Use Ledger hardware wallet
+        #[arg(long = "ledger", short = 'l', conflicts_with = "trezor")]
+        ledger: bool,
+    },
+    /// Revoke roles and zero the root of a strategist, and remove it from config.toml
+    Remove {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// Strategist address
+        #[arg(long = "strategist", short = 's')]
+        strategist: String,
+
+        /// Roles to revoke, by name or id
+        #[arg(long = "role")]
+        roles: Vec<String>,
+
+        /// Nonce, fetched from the multisig if not set
+        #[arg(long = "nonce")]
+        nonce: Option<u32>,
+
+        /// Use Trezor hardware wallet
+        #[arg(long = "trezor", short = 't', conflicts_with = "ledger")]
+        trezor: bool,
+
+        /// Use Ledger hardware wallet
+        #[arg(long = "ledger", short = 'l', conflicts_with = "trezor")]
+        ledger: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Set a value of an existing product entry
+    Set {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID, the default entry if not set
+        #[arg(long = "network", short = 'n')]
+        network_id: Option<u32>,
+
+        /// Key to set, ie vault_address
+        #[arg(long = "key", short = 'k')]
+        key: String,
+
+        /// New value, addresses are checksummed
+        #[arg(long = "value", short = 'v')]
+        value: String,
+    },
+    /// Add a network entry to an existing product
+    AddNetwork {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// Values of the entry as key=value, ie multisig_address=0x...
+        #[arg(long = "value", short = 'v', required = true)]
+        values: Vec<String>,
+    },
+    /// Add a new product
+    AddProduct {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Network ID, a default entry if not set
+        #[arg(long = "network", short = 'n')]
+        network_id: Option<u32>,
+
+        /// Values of the entry as key=value, ie multisig_address=0x...
+        #[arg(long = "value", short = 'v', required = true)]
+        values: Vec<String>,
+    },
+    /// Find the contracts of a product on chain from its vault
+    Discover {
+        /// Vault address
+        #[arg(long = "vault")]
+        vault: String,
+
+        /// Network ID
+        #[arg(long = "network", short = 'n')]
+        network_id: u32,
+
+        /// Product name, used in the printed block and required to insert it
+        #[arg(long = "product", short = 'p', required_if_eq("insert", "true"))]
+        product: Option<String>,
+
+        /// First block scanned for roles authority and timelock events
+        #[arg(long = "from-block", default_value_t = 0)]
+        from_block: u64,
+
+        /// Insert the discovered entry into config.toml
+        #[arg(long = "insert")]
+        insert: bool,
+    },
+    /// Remove a product and every one of its entries
+    RemoveProduct {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum EmergencyCommands {
+    /// Pause the teller, queue, accountant and manager of a product on every network it is on
+    Pause {
+        /// Product name
+        #[arg(long = "product", short = 'p')]
+        product: String,
+
+        /// Networks to pause, every network of the product in config.toml if not set
+        #[arg(long = "network", short = 'n')]
+        network_ids: Vec<u32>,
+    },
 }
 
 #[tokio::main]
@@ -222,7 +524,8 @@ async fn main() -> Result<()> {
         } => {
             // Generate the transaction configs
             let (configs, strategists) =
-                generate_root_update_txs(&root, &product, *network_id, *nonce).await?;
+                generate_root_update_txs(root, product, *network_id, *nonce).await?;
+            let writer = ConfigWrapper::from_file(None)?.get_report_writer()?;
 
             // Process based on number of configs
             match configs.len() {
@@ -239,6 +542,7 @@ async fn main() -> Result<()> {
                     println!("\nSimulation URL: {}", simulation_url);
 
                     // Ask user if they want to approve
+                    let mut tx_url = None;
                     if prompt_user_confirmation("Would you like to approve this transaction?")? {
                         let wallet_type = if *trezor {
                             HardwareWalletType::TREZOR
@@ -249,20 +553,26 @@ async fn main() -> Result<()> {
                             HardwareWalletType::LEDGER
                         };
 
-                        let tx_url = approve_hash("output/single.json", wallet_type).await?;
-
-                        // Generate and print summary
-                        print_transaction_summary(
-                            &product,
-                            *network_id,
-                            &configs[0],
-                            &root,
-                            &safe_hash,
-                            &strategists,
-                            &tx_url,
-                            simulation_url,
-                        )?;
+                        tx_url = Some(approve_hash("output/single.json", wallet_type).await?);
                     }
+
+                    // Save the report, printing it if the tx was approved
+                    let report = root_update_report(
+                        product,
+                        None,
+                        &configs[0],
+                        root,
+                        &safe_hash,
+                        &strategists,
+                        tx_url,
+                        simulation_url,
+                    )?;
+                    save_transaction_report(
+                        &writer,
+                        &report,
+                        "output/single.json",
+                        report.proposal_url.is_some(),
+                    )?;
                 }
                 2 => {
                     // Save both tx configs
@@ -286,6 +596,7 @@ async fn main() -> Result<()> {
                     println!("\nSimulation URL: {}", simulation_url);
 
                     // Handle first transaction
+                    let mut tx_url = None;
                     if prompt_user_confirmation(
                         "Would you like to approve the propose transaction?",
                     )? {
@@ -295,22 +606,29 @@ async fn main() -> Result<()> {
                             HardwareWalletType::LEDGER
                         };
 
-                        let tx_url = approve_hash("output/propose.json", wallet_type).await?;
-
-                        // Print summary for propose tx
-                        print_transaction_summary(
-                            &product,
-                            *network_id,
-                            &configs[0],
-                            &root,
-                            &propose_hash,
-                            &strategists,
-                            &tx_url,
-                            simulation_url.clone(),
-                        )?;
+                        tx_url = Some(approve_hash("output/propose.json", wallet_type).await?);
                     }
 
+                    // Save the report for the propose tx
+                    let report = root_update_report(
+                        product,
+                        Some("Propose"),
+                        &configs[0],
+                        root,
+                        &propose_hash,
+                        &strategists,
+                        tx_url,
+                        simulation_url.clone(),
+                    )?;
+                    save_transaction_report(
+                        &writer,
+                        &report,
+                        "output/propose.json",
+                        report.proposal_url.is_some(),
+                    )?;
+
                     // Handle second transaction
+                    let mut tx_url = None;
                     if prompt_user_confirmation(
                         "Would you like to approve the execute transaction?",
                     )? {
@@ -320,20 +638,26 @@ async fn main() -> Result<()> {
                             HardwareWalletType::LEDGER
                         };
 
-                        let tx_url = approve_hash("output/execute.json", wallet_type).await?;
-
-                        // Print summary for execute tx
-                        print_transaction_summary(
-                            &product,
-                            *network_id,
-                            &configs[1],
-                            &root,
-                            &execute_hash,
-                            &strategists,
-                            &tx_url,
-                            simulation_url,
-                        )?;
+                        tx_url = Some(approve_hash("output/execute.json", wallet_type).await?);
                     }
+
+                    // Save the report for the execute tx
+                    let report = root_update_report(
+                        product,
+                        Some("Execute"),
+                        &configs[1],
+                        root,
+                        &execute_hash,
+                        &strategists,
+                        tx_url,
+                        simulation_url,
+                    )?;
+                    save_transaction_report(
+                        &writer,
+                        &report,
+                        "output/execute.json",
+                        report.proposal_url.is_some(),
+                    )?;
                 }
                 _ => return Err(eyre!("Unexpected number of transactions generated")),
             }
@@ -347,176 +671,345 @@ async fn main() -> Result<()> {
             let file_content = fs::read_to_string(tx_path)?;
             let json_value: Value = serde_json::from_str(&file_content)?;
 
-            // Generate the configs and descriptions
-            let (configs, descriptions) =
-                generate_admin_actions_from_json(json_value.clone()).await?;
+            process_action_file(json_value, *trezor, *ledger).await?;
+        }
+        Commands::RotateOwner {
+            product,
+            network_id,
+            new_owner,
+            new_authority,
+            nonce,
+            update_config,
+            skip_foreign_owned,
+            trezor,
+            ledger,
+        } => {
+            // Batches of timelocked products are routed through timelock_address, which would no
+            // longer own anything once the multisig is rotated.
+            if *update_config
+                && let Ok(timelock) = ConfigWrapper::from_file(None)?.get_product_config_value(
+                    product,
+                    *network_id,
+                    "timelock_address",
+                )
+            {
+                return Err(eyre!(
+                    "{} on {} is behind timelock {}, --update-config can not be used, update multisig_address and timelock_address in config.toml by hand",
+                    product,
+                    network_id,
+                    timelock
+                ));
+            }
 
-            // Process based on number of configs
-            match configs.len() {
-                1 => {
-                    // Save tx config to file
-                    fs::write(
-                        "output/single.json",
-                        serde_json::to_string_pretty(&configs[0])?,
-                    )?;
+            let mut rotation = json!({
+                "new_owner": new_owner,
+                "skip_foreign_owned": skip_foreign_owned
+            });
+            if let Some(new_authority) = new_authority {
+                rotation["new_authority"] = json!(new_authority);
+            }
+            let mut action_file = json!({
+                "network_id": network_id,
+                "actions": [{ "product": product, "rotate_owner": rotation }]
+            });
+            if let Some(nonce) = nonce {
+                action_file["nonce"] = json!(nonce);
+            }
 
-                    // Simulate single tx
-                    let (simulation_url, safe_hash) =
-                        simulate_admin_tx_and_generate_safe_hash("output/single.json").await?;
+            let batches = generate_admin_batches_from_json(action_file.clone()).await?;
 
-                    if *trezor || *ledger {
-                        // Ask user if they want to approve
-                        if prompt_user_confirmation("Would you like to approve this transaction?")?
-                        {
-                            let wallet_type = if *trezor {
-                                HardwareWalletType::TREZOR
-                            } else {
-                                if !*ledger {
-                                    return Err(eyre!("Please select a hardware wallet to use"));
-                                }
-                                HardwareWalletType::LEDGER
-                            };
-
-                            let tx_url = approve_hash("output/single.json", wallet_type).await?;
-
-                            println!("\n# Action Configuration");
-                            println!("```json");
-                            println!("{}", serde_json::to_string_pretty(&json_value)?);
-                            println!("```\n");
-
-                            print_advanced_transaction_summary(
-                                None,
-                                &configs[0],
-                                descriptions[0].clone(),
-                                &safe_hash,
-                                Some(&tx_url),
-                                simulation_url,
-                            )?;
-                        }
-                    } else {
-                        println!("\n# Action Configuration");
-                        println!("```json");
-                        println!("{}", serde_json::to_string_pretty(&json_value)?);
-                        println!("```\n");
-                        print_advanced_transaction_summary(
-                            None,
-                            &configs[0],
-                            descriptions[0].clone(),
-                            &safe_hash,
-                            None,
-                            simulation_url,
-                        )?;
-                    }
+            // Check the new owner can still administer every rotated contract before anything is
+            // approved.
+            for batch in &batches {
+                let checks = simulate_owner_rotation_post_check(batch, product, new_owner).await?;
+                println!("\n# Post Rotation Check");
+                for (key, address, success) in &checks {
+                    let status = if *success { "ok" } else { "FAILED" };
+                    println!("{} {}: {}", key, address, status);
                 }
-                2 => {
-                    // Save both tx configs
-                    fs::write(
-                        "output/propose.json",
-                        serde_json::to_string_pretty(&configs[0])?,
-                    )?;
-                    fs::write(
-                        "output/execute.json",
-                        serde_json::to_string_pretty(&configs[1])?,
-                    )?;
+                if checks.iter().any(|(_, _, success)| !success) {
+                    return Err(eyre!(
+                        "New owner {} cannot administer every contract after the rotation",
+                        new_owner
+                    ));
+                }
+            }
 
-                    // Simulate timelock txs
-                    let (simulation_url, propose_hash, execute_hash) =
-                        simulate_timelock_admin_txs_and_generate_safe_hashes(
-                            "output/propose.json".to_string(),
-                            "output/execute.json".to_string(),
-                        )
-                        .await?;
+            fs::create_dir_all("output")?;
 
-                    println!("\nSimulation URL: {}", simulation_url);
+            println!("\n# Action Configuration");
+            println!("```json");
+            println!("{}", serde_json::to_string_pretty(&action_file)?);
+            println!("```\n");
 
-                    if *trezor || *ledger {
-                        let send_propose = prompt_user_confirmation(
-                            "Would you like to approve the propose transaction?",
-                        )?;
-                        let send_execute = prompt_user_confirmation(
-                            "Would you like to approve the execute transaction?",
-                        )?;
-
-                        // Handle first transaction
-                        if send_propose {
-                            let wallet_type = if *trezor {
-                                HardwareWalletType::TREZOR
-                            } else {
-                                HardwareWalletType::LEDGER
-                            };
-
-                            let tx_url = approve_hash("output/propose.json", wallet_type).await?;
-
-                            println!("\n# Action Configuration");
-                            println!("```json");
-                            println!("{}", serde_json::to_string_pretty(&json_value)?);
-                            println!("```\n");
-
-                            // Print summary for propose tx
-                            print_advanced_transaction_summary(
-                                Some("Propose".to_string()),
-                                &configs[0],
-                                descriptions[0].clone(),
-                                &propose_hash,
-                                Some(&tx_url),
-                                simulation_url.clone(),
-                            )?;
-                        }
+            for batch in &batches {
+                process_admin_batch(batch, *trezor, *ledger).await?;
+            }
 
-                        // Handle second transaction
-                        if send_execute {
-                            let wallet_type = if *trezor {
-                                HardwareWalletType::TREZOR
-                            } else {
-                                HardwareWalletType::LEDGER
-                            };
-
-                            let tx_url = approve_hash("output/execute.json", wallet_type).await?;
-
-                            if !send_propose {
-                                println!("\n# Action Configuration");
-                                println!("```json");
-                                println!("{}", serde_json::to_string_pretty(&json_value)?);
-                                println!("```\n");
-                            }
+            if *update_config {
+                let mut editor = ConfigEditor::from_file(None)?;
+                editor.set_product_network_value(
+                    product,
+                    *network_id,
+                    "multisig_address",
+                    new_owner,
+                )?;
+                editor.save()?;
+                println!(
+                    "multisig_address of {} on {} set to {} in config.toml",
+                    product, network_id, new_owner
+                );
+            }
+        }
+        Commands::RotateSolver {
+            old_solver,
+            new_solver,
+            allow_self_solves,
+            template,
+            old_template,
+            network_ids,
+            trezor,
+            ledger,
+        } => {
+            let cw = ConfigWrapper::from_file(None)?;
+            let action_file = rotate_solver_action_file(
+                &cw,
+                old_solver,
+                new_solver,
+                *allow_self_solves,
+                template.as_deref(),
+                old_template.as_deref(),
+                network_ids,
+            )
+            .await?;
+
+            fs::create_dir_all("output")?;
+            let path = "output/rotate_solver.json";
+            fs::write(path, serde_json::to_string_pretty(&action_file)?)?;
+            println!("Saved action file to: {}", path);
 
-                            // Print summary for execute tx
-                            print_advanced_transaction_summary(
-                                Some("Execute".to_string()),
-                                &configs[1],
-                                descriptions[1].clone(),
-                                &execute_hash,
-                                Some(&tx_url),
-                                simulation_url,
-                            )?;
+            process_action_file(action_file, *trezor, *ledger).await?;
+        }
+        Commands::Strategist { command } => match command {
+            StrategistCommands::Add {
+                product,
+                network_id,
+                strategist,
+                roles,
+                root,
+                nonce,
+                trezor,
+                ledger,
+            } => {
+                let update = json!({
+                    "strategist_address": strategist,
+                    "operation": "add_roles",
+                    "roles": parse_roles(roles),
+                    "root": root,
+                });
+                let reports = process_action_file(
+                    strategist_action_file(product, *network_id, *nonce, update),
+                    *trezor,
+                    *ledger,
+                )
+                .await?;
+                if !reports.iter().all(|report| report.approved) {
+                    println!("\nconfig.toml left unchanged since not every tx was approved");
+                    return Ok(());
+                }
+
+                let mut editor = ConfigWrapper::from_file(None)?.editor()?;
+                if editor.add_product_strategist(product, *network_id, strategist)? {
+                    editor.save()?;
+                    println!(
+                        "Added {} to the strategists of {} on {} in config.toml",
+                        strategist, product, network_id
+                    );
+                } else {
+                    println!(
+                        "{} is already a strategist of {} on {} in config.toml",
+                        strategist, product, network_id
+                    );
+                }
+            }
+            StrategistCommands::Remove {
+                product,
+                network_id,
+                strategist,
+                roles,
+                nonce,
+                trezor,
+                ledger,
+            } => {
+                let update = json!({
+                    "strategist_address": strategist,
+                    "operation": "revoke_roles",
+                    "roles": parse_roles(roles),
+                });
+                let reports = process_action_file(
+                    strategist_action_file(product, *network_id, *nonce, update),
+                    *trezor,
+                    *ledger,
+                )
+                .await?;
+                if !reports.iter().all(|report| report.approved) {
+                    println!("\nconfig.toml left unchanged since not every tx was approved");
+                    return Ok(());
+                }
+
+                let mut editor = ConfigWrapper::from_file(None)?.editor()?;
+                if editor.remove_product_strategist(product, *network_id, strategist)? {
+                    editor.save()?;
+                    println!(
+                        "Removed {} from the strategists of {} on {} in config.toml",
+                        strategist, product, network_id
+                    );
+                } else {
+                    println!(
+                        "WARNING: {} is not a strategist of {} on {} in config.toml",
+                        strategist, product, network_id
+                    );
+                }
+            }
+        },
+        Commands::Config { command } => {
+            let cw = ConfigWrapper::from_file(None)?;
+            let mut editor = cw.editor()?;
+            match command {
+                ConfigCommands::Set {
+                    product,
+                    network_id,
+                    key,
+                    value,
+                } => {
+                    editor.set_product_value(product, *network_id, key, value)?;
+                    println!("{} of {} set to {}", key, product, value);
+                }
+                ConfigCommands::AddNetwork {
+                    product,
+                    network_id,
+                    values,
+                } => {
+                    editor.add_product_network(
+                        product,
+                        Some(*network_id),
+                        &parse_key_values(values)?,
+                    )?;
+                    println!("Added network {} to {}", network_id, product);
+                }
+                ConfigCommands::AddProduct {
+                    product,
+                    network_id,
+                    values,
+                } => {
+                    editor.add_product(product, *network_id, &parse_key_values(values)?)?;
+                    println!("Added product {}", product);
+                }
+                ConfigCommands::Discover {
+                    vault,
+                    network_id,
+                    product,
+                    from_block,
+                    insert,
+                } => {
+                    let discovered = discover_product(&cw, *network_id, vault, *from_block).await?;
+                    let values = discovered.config_values();
+
+                    let missing = discovered.missing();
+
+                    println!(
+                        "\n[product.{}.{}]",
+                        product.as_deref().unwrap_or("<product>"),
+                        network_id
+                    );
+                    if !missing.is_empty() {
+                        println!("# Not found: {}", missing.join(", "));
+                    }
+                    for (key, value) in &values {
+                        println!("{} = \"{}\"", key, value);
+                    }
+                    println!();
+                    for name in &missing {
+                        println!("WARNING: no {} found", name);
+                    }
+
+                    if *insert && let Some(product) = product {
+                        if cw.get_products().contains(product) {
+                            editor.add_product_network(product, Some(*network_id), &values)?;
+                        } else {
+                            editor.add_product(product, Some(*network_id), &values)?;
+                        }
+                        println!(
+                            "Inserted product.{}.{} into config.toml",
+                            product, network_id
+                        );
+                        if !missing.is_empty() {
+                            println!(
+                                "WARNING: the inserted entry has no {}, add them by hand",
+                                missing.join(", ")
+                            );
                         }
-                    } else {
-                        // Print summaries for both transactions without sending them
-                        println!("\n# Action Configuration");
-                        println!("```json");
-                        println!("{}", serde_json::to_string_pretty(&json_value)?);
-                        println!("```\n");
-
-                        print_advanced_transaction_summary(
-                            Some("Propose".to_string()),
-                            &configs[0],
-                            descriptions[0].clone(),
-                            &propose_hash,
-                            None,
-                            simulation_url.clone(),
-                        )?;
-
-                        print_advanced_transaction_summary(
-                            Some("Execute".to_string()),
-                            &configs[1],
-                            descriptions[1].clone(),
-                            &execute_hash,
-                            None,
-                            simulation_url,
-                        )?;
                     }
                 }
-                _ => return Err(eyre!("Unexpected number of transactions generated")),
+                ConfigCommands::RemoveProduct { product } => {
+                    editor.remove_product(product)?;
+                    println!("Removed product {}", product);
+                }
+            }
+            editor.save()?;
+        }
+        Commands::Permissions {
+            product,
+            network_id,
+            format,
+            from_block,
+            output,
+        } => {
+            let export =
+                export_permission_matrix(product, *network_id, format, *from_block).await?;
+            match output {
+                Some(path) => {
+                    fs::write(path, export)?;
+                    println!("Saved permissions to: {}", path);
+                }
+                None => println!("{}", export),
+            }
+        }
+        Commands::Decode { data } => {
+            println!("{}", serde_json::to_string_pretty(&decode_calldata(data)?)?);
+        }
+        Commands::Emergency { command } => match command {
+            EmergencyCommands::Pause {
+                product,
+                network_ids,
+            } => {
+                emergency_pause(product, network_ids).await?;
+            }
+        },
+        Commands::ProbeVersions {
+            product,
+            network_id,
+            dry_run,
+        } => {
+            let cw = ConfigWrapper::from_file(None)?;
+            let versions = probe_contract_versions(&cw, product, *network_id).await?;
+
+            let display = |v: Option<String>| v.unwrap_or_else(|| "not configured".to_string());
+            println!(
+                "Teller: {}",
+                display(versions.teller.map(|v| v.to_string()))
+            );
+            println!(
+                "Accountant: {}",
+                display(versions.accountant.map(|v| v.to_string()))
+            );
+            println!("Queue: {}", display(versions.queue.map(|v| v.to_string())));
+
+            if !*dry_run {
+                let mut editor = ConfigEditor::from_file(None)?;
+                editor.set_product_versions(product, *network_id, &versions)?;
+                editor.save()?;
+                println!("Versions written to config.toml");
             }
         }
     }
@@ -524,85 +1017,485 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-// Helper function to prompt user for confirmation
-fn prompt_user_confirmation(message: &str) -> Result<bool> {
-    println!("\n{} (y/n)", message);
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
-    Ok(input.trim().to_lowercase() == "y")
+// Helper function to generate, simulate and optionally approve every batch of an action file
+async fn process_action_file(
+    json_value: Value,
+    trezor: bool,
+    ledger: bool,
+) -> Result<Vec<BatchReport>> {
+    // Generate one batch of txs per network
+    let batches = generate_admin_batches_from_json(json_value.clone()).await?;
+
+    fs::create_dir_all("output")?;
+
+    println!("\n# Action Configuration");
+    println!("```json");
+    println!("{}", serde_json::to_string_pretty(&json_value)?);
+    println!("```\n");
+
+    let mut reports = Vec::with_capacity(batches.len());
+    for batch in &batches {
+        reports.push(process_admin_batch(batch, trezor, ledger).await?);
+    }
+
+    if reports.len() > 1 {
+        print_combined_report(&reports);
+    }
+
+    Ok(reports)
 }
 
-// Helper function to print transaction summary
-fn print_transaction_summary(
+// Splits command line values given as key=value.
+fn parse_key_values(values: &[String]) -> Result<Vec<(String, String)>> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .split_once('=')
+                .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                .ok_or_else(|| eyre!("{} must be given as key=value", value))
+        })
+        .collect()
+}
+
+// Builds a single network action file holding one strategist update.
+fn strategist_action_file(
     product: &str,
     network_id: u32,
-    tx_config: &SimulationConfig,
-    root: &str,
-    safe_hash: &str,
-    strategists: &Vec<String>,
-    tx_url: &str,
+    nonce: Option<u32>,
+    update: Value,
+) -> Value {
+    let mut action_file = json!({
+        "network_id": network_id,
+        "actions": [{ "product": product, "update_strategist": update }]
+    });
+    if let Some(nonce) = nonce {
+        action_file["nonce"] = json!(nonce);
+    }
+    action_file
+}
+
+// Roles given on the command line, ids are passed as numbers and anything else as role names.
+fn parse_roles(roles: &[String]) -> Vec<Value> {
+    roles
+        .iter()
+        .map(|role| match role.parse::<u64>() {
+            Ok(id) => json!(id),
+            Err(_) => json!(role),
+        })
+        .collect()
+}
+
+// Builds an action file tearing down the old solver and setting up the new one on every product
+// and network with a queue. Products sharing a roles authority on a network are only updated once,
+// and products whose roles authority never granted the old solver any role are skipped. The old
+// solver is torn down with its own template, tear downs always revoking self solves.
+async fn rotate_solver_action_file(
+    cw: &ConfigWrapper,
+    old_solver: &str,
+    new_solver: &str,
+    allow_self_solves: bool,
+    template: Option<&str>,
+    old_template: Option<&str>,
+    network_ids: &[u32],
+) -> Result<Value> {
+    let solver_update = |mode: &str, solver: &str, template: Option<&str>| {
+        let mut update = json!({ mode: true, "solver_contract": solver });
+        if mode == "setup" {
+            update["allow_self_solves"] = json!(allow_self_solves);
+        }
+        if let Some(template) = template {
+            update["template"] = json!(template);
+        }
+        update
+    };
+
+    let mut networks: Vec<(u32, Vec<Value>)> = Vec::new();
+    let mut seen_authorities = Vec::new();
+    for product in cw.get_products() {
+        for network_id in cw.get_product_networks(&product)? {
+            if !network_ids.is_empty() && !network_ids.contains(&network_id) {
+                continue;
+            }
+            let has_queue = cw
+                .get_product_config_value(&product, network_id, "queue_address")
+                .is_ok_and(|queue| !is_unset_address(&queue));
+            let Ok(roles_authority) =
+                cw.get_product_config_value(&product, network_id, "roles_authority_address")
+            else {
+                continue;
+            };
+            if !has_queue
+                || seen_authorities.contains(&(network_id, roles_authority.to_lowercase()))
+            {
+                continue;
+            }
+            seen_authorities.push((network_id, roles_authority.to_lowercase()));
+
+            if !has_any_role(network_id, &roles_authority, old_solver).await? {
+                println!(
+                    "Skipping {} on {}, roles authority {} never granted {} a role",
+                    product, network_id, roles_authority, old_solver
+                );
+                continue;
+            }
+
+            let actions = vec![
+                json!({
+                    "product": product,
+                    "update_solver": solver_update("tear_down", old_solver, old_template)
+                }),
+                json!({
+                    "product": product,
+                    "update_solver": solver_update("setup", new_solver, template)
+                }),
+            ];
+            match networks.iter_mut().find(|(n, _)| *n == network_id) {
+                Some((_, network_actions)) => network_actions.extend(actions),
+                None => networks.push((network_id, actions)),
+            }
+        }
+    }
+
+    if networks.is_empty() {
+        return Err(eyre!(
+            "No product with a queue where {} holds a role found",
+            old_solver
+        ));
+    }
+    networks.sort_by_key(|(network_id, _)| *network_id);
+
+    Ok(json!({
+        "networks": networks
+            .into_iter()
+            .map(|(network_id, actions)| json!({ "network_id": network_id, "actions": actions }))
+            .collect::<Vec<_>>()
+    }))
+}
+
+// Config addresses left empty or zeroed for contracts a product does not have.
+fn is_unset_address(address: &str) -> bool {
+    address.len() <= 2 || address.trim_start_matches("0x").chars().all(|c| c == '0')
+}
+
+// Result of saving, simulating and optionally approving a single admin batch.
+struct BatchReport {
+    network_id: u32,
+    multisig: String,
+    products: String,
+    // (tx name, nonce, safe hash, tx path)
+    txs: Vec<(String, u32, String, String)>,
     simulation_url: String,
-) -> Result<()> {
-    println!("\n# {} (Network: {})", product, network_id);
-    println!("\n## Transaction Data");
-    println!("```json");
-    println!("{}", serde_json::to_string_pretty(tx_config)?);
-    println!("```");
+    // Whether every tx of the batch was approved with a hardware wallet.
+    approved: bool,
+}
+
+// Helper function to save, simulate and optionally approve the txs of a single batch
+async fn process_admin_batch(
+    batch: &AdminBatch,
+    trezor: bool,
+    ledger: bool,
+) -> Result<BatchReport> {
+    println!(
+        "\n# Network {} | Multisig {} | Products {}",
+        batch.network_id,
+        batch.multisig,
+        batch.products.join(", ")
+    );
+
+    let names = batch.tx_names();
+    let paths = batch.tx_paths();
+
+    // Save tx configs to files
+    for (path, config) in paths.iter().zip(&batch.configs) {
+        fs::write(path, serde_json::to_string_pretty(config)?)?;
+    }
+
+    let (simulation_url, safe_hashes) = match batch.configs.len() {
+        0 => return Err(eyre!("Unexpected number of transactions generated")),
+        1 => {
+            // Simulate single tx
+            let (simulation_url, safe_hash) =
+                simulate_admin_tx_and_generate_safe_hash(&paths[0]).await?;
+            (simulation_url, vec![safe_hash])
+        }
+        _ => {
+            // Simulate txs one after the other, past the timelock delay if there is one
+            let (simulation_url, safe_hashes) =
+                simulate_admin_txs_in_sequence(&paths, batch.execute_from).await?;
+            println!("\nSimulation URL: {}", simulation_url);
+            (simulation_url, safe_hashes)
+        }
+    };
+
+    let mut tx_urls = vec![None; batch.configs.len()];
+    if trezor || ledger {
+        // Ask user which txs they want to approve
+        let mut approvals = Vec::with_capacity(names.len());
+        for name in &names {
+            let message = if names.len() == 1 {
+                "Would you like to approve this transaction?".to_string()
+            } else {
+                format!(
+                    "Would you like to approve the {} transaction?",
+                    name.to_lowercase()
+                )
+            };
+            approvals.push(prompt_user_confirmation(&message)?);
+        }
+
+        for (i, approved) in approvals.into_iter().enumerate() {
+            if approved {
+                let wallet_type = if trezor {
+                    HardwareWalletType::TREZOR
+                } else {
+                    HardwareWalletType::LEDGER
+                };
+                tx_urls[i] = Some(approve_hash(&paths[i], wallet_type).await?);
+            }
+        }
+    }
 
-    println!("\n## Strategists");
-    for (i, strategist) in strategists.iter().enumerate() {
-        println!("{}. {}", i + 1, strategist);
+    // Save reports for every transaction, printing the ones that were approved, or all of them if
+    // no hardware wallet was selected.
+    let writer = ConfigWrapper::from_file(None)?.get_report_writer()?;
+    for (i, config) in batch.configs.iter().enumerate() {
+        let title = match batch.configs.len() {
+            1 => "Transaction Summary".to_string(),
+            _ => format!("{} Transaction Summary", names[i]),
+        };
+        let mut report = TransactionReport::new(&title, config, &safe_hashes[i])?;
+        report.products = batch.products.clone();
+        report.set_actions(&batch.descriptions[i])?;
+        report.proposal_url = tx_urls[i].clone();
+        report.simulation_url = Some(simulation_url.clone());
+        save_transaction_report(
+            &writer,
+            &report,
+            &paths[i],
+            tx_urls[i].is_some() || !(trezor || ledger),
+        )?;
     }
 
-    println!("\n## New Root");
-    println!("`{}`", root);
+    let txs = names
+        .into_iter()
+        .zip(&batch.configs)
+        .zip(safe_hashes)
+        .zip(paths)
+        .map(|(((name, config), safe_hash), path)| (name, config.nonce, safe_hash, path))
+        .collect();
+
+    Ok(BatchReport {
+        network_id: batch.network_id,
+        multisig: batch.multisig.to_string(),
+        products: batch.products.join(", "),
+        txs,
+        simulation_url,
+        approved: tx_urls.iter().all(|url| url.is_some()),
+    })
+}
+
+// Generates one pause batch per network of the product, then simulates them all in parallel and
+// prints the safe hashes to sign, saving a report next to every tx file. Networks that fail are
+// reported without stopping the others.
+async fn emergency_pause(product: &str, network_ids: &[u32]) -> Result<()> {
+    let cw = ConfigWrapper::from_file(None)?;
+    let network_ids = match network_ids.is_empty() {
+        true => cw.get_product_networks(product)?,
+        false => network_ids.to_vec(),
+    };
+
+    fs::create_dir_all("output")?;
+
+    let mut failures = Vec::new();
+    let mut batches = Vec::new();
+    for network_id in network_ids {
+        let action_file = json!({
+            "network_id": network_id,
+            "actions": [{ "product": product, "emergency_pause": true }]
+        });
+        match generate_admin_batches_from_json(action_file).await {
+            Ok(network_batches) => batches.extend(network_batches),
+            Err(e) => failures.push((network_id, e.to_string())),
+        }
+    }
 
-    println!("\n## Safe Hash");
-    println!("`{}`", safe_hash);
+    let mut simulations = JoinSet::new();
+    for (index, batch) in batches.iter().enumerate() {
+        let paths = batch.tx_paths();
+        for (path, config) in paths.iter().zip(&batch.configs) {
+            fs::write(path, serde_json::to_string_pretty(config)?)?;
+        }
+        let execute_from = batch.execute_from;
+        simulations.spawn(async move {
+            let result = match paths.len() {
+                1 => simulate_admin_tx_and_generate_safe_hash(&paths[0])
+                    .await
+                    .map(|(simulation_url, safe_hash)| (simulation_url, vec![safe_hash])),
+                _ => simulate_admin_txs_in_sequence(&paths, execute_from).await,
+            };
+            (index, result.map_err(|e| e.to_string()))
+        });
+    }
 
-    println!("\n## Links");
-    println!("- [Proposal Transaction]({})", tx_url);
-    println!("- [Simulation]({})", simulation_url);
+    let mut results = vec![None; batches.len()];
+    while let Some(joined) = simulations.join_next().await {
+        let (index, result) = joined?;
+        results[index] = Some(result);
+    }
+
+    // Save the reports of every simulated tx next to its file.
+    let writer = cw.get_report_writer()?;
+    for (batch, result) in batches.iter().zip(&results) {
+        let Some(Ok((simulation_url, safe_hashes))) = result else {
+            continue;
+        };
+        for (i, (config, safe_hash)) in batch.configs.iter().zip(safe_hashes).enumerate() {
+            let title = format!(
+                "Emergency Pause {} {} Transaction Summary (Network: {})",
+                product,
+                batch.tx_names()[i],
+                batch.network_id
+            );
+            let mut report = TransactionReport::new(&title, config, safe_hash)?;
+            report.products = batch.products.clone();
+            report.set_actions(&batch.descriptions[i])?;
+            report.simulation_url = Some(simulation_url.clone());
+            save_transaction_report(&writer, &report, &batch.tx_paths()[i], false)?;
+        }
+    }
+
+    println!("\n# Emergency Pause {}", product);
+    println!("\n| Network | Multisig | Timelocked | Tx | Nonce | Safe Hash | File | Simulation |");
+    println!("| --- | --- | --- | --- | --- | --- | --- | --- |");
+    for (batch, result) in batches.iter().zip(results) {
+        // Timelocked pauses only take effect once the execute tx can be executed.
+        let timelocked = match batch.execute_from {
+            Some(_) => "YES",
+            None => "no",
+        };
+        match result {
+            Some(Ok((simulation_url, safe_hashes))) => {
+                for (((name, config), safe_hash), path) in batch
+                    .tx_names()
+                    .iter()
+                    .zip(&batch.configs)
+                    .zip(&safe_hashes)
+                    .zip(batch.tx_paths())
+                {
+                    println!(
+                        "| {} | `{}` | {} | {} | {} | `{}` | `{}` | [link]({}) |",
+                        batch.network_id,
+                        batch.multisig,
+                        timelocked,
+                        name,
+                        config.nonce,
+                        safe_hash,
+                        path,
+                        simulation_url
+                    );
+                }
+            }
+            Some(Err(e)) => failures.push((batch.network_id, format!("Simulation failed: {}", e))),
+            None => failures.push((batch.network_id, "Simulation did not run".to_string())),
+        }
+    }
+
+    if batches.iter().any(|batch| batch.execute_from.is_some()) {
+        println!(
+            "\nWARNING: timelocked networks only pause once the timelock delay has passed after the propose tx"
+        );
+    }
+
+    if !failures.is_empty() {
+        println!("\n## Failed Networks");
+        for (network_id, error) in &failures {
+            println!("- {}: {}", network_id, error);
+        }
+        return Err(eyre!(
+            "Emergency pause failed on {} network(s)",
+            failures.len()
+        ));
+    }
 
     Ok(())
 }
 
-fn print_advanced_transaction_summary(
-    tx_name: Option<String>,
+// Helper function to print a single overview of every batch in an action file
+fn print_combined_report(reports: &[BatchReport]) {
+    println!("\n# Combined Report");
+    println!("\n| Network | Multisig | Products | Tx | Nonce | Safe Hash | File | Simulation |");
+    println!("| --- | --- | --- | --- | --- | --- | --- | --- |");
+    for report in reports {
+        for (name, nonce, safe_hash, path) in &report.txs {
+            println!(
+                "| {} | `{}` | {} | {} | {} | `{}` | `{}` | [link]({}) |",
+                report.network_id,
+                report.multisig,
+                report.products,
+                name,
+                nonce,
+                safe_hash,
+                path,
+                report.simulation_url
+            );
+        }
+    }
+}
+
+// Helper function to prompt user for confirmation
+fn prompt_user_confirmation(message: &str) -> Result<bool> {
+    println!("\n{} (y/n)", message);
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_lowercase() == "y")
+}
+
+// Helper function to build the report of a root update tx
+#[allow(clippy::too_many_arguments)]
+fn root_update_report(
+    product: &str,
+    tx_name: Option<&str>,
     tx_config: &SimulationConfig,
-    descriptions: Vec<String>,
+    root: &str,
     safe_hash: &str,
-    tx_url: Option<&str>,
+    strategists: &[String],
+    tx_url: Option<String>,
     simulation_url: String,
-) -> Result<()> {
-    // Build the title based on tx_name and network_id
+) -> Result<TransactionReport> {
     let title = match tx_name {
-        Some(name) => format!("\n# {} Transaction Summary ", name),
-        None => format!("\n# Transaction Summary "),
+        Some(name) => format!(
+            "{} {} Root Update (Network: {})",
+            product, name, tx_config.network_id
+        ),
+        None => format!(
+            "{} Root Update (Network: {})",
+            product, tx_config.network_id
+        ),
     };
-    println!("{}", title);
-
-    println!("\n## Transaction Data");
-    println!("```json");
-    println!("{}", serde_json::to_string_pretty(&tx_config)?);
-    println!("```");
+    let mut report = TransactionReport::new(&title, tx_config, safe_hash)?;
+    report.products = vec![product.to_string()];
+    report.root_update = Some(RootUpdate {
+        root: root.to_string(),
+        strategists: strategists.to_vec(),
+    });
+    report.proposal_url = tx_url;
+    report.simulation_url = Some(simulation_url);
+    Ok(report)
+}
 
-    println!("\n## Actions");
-    println!("```json");
-    for desc in descriptions.iter() {
-        println!("{}", desc);
+// Helper function to write the reports of a tx next to its file, printing the markdown one if asked
+fn save_transaction_report(
+    writer: &ReportWriter,
+    report: &TransactionReport,
+    tx_path: &str,
+    print: bool,
+) -> Result<()> {
+    if print {
+        println!("\n{}", writer.render(report, ReportFormat::Markdown)?);
     }
-    println!("```");
-
-    println!("\n## Safe Hash");
-    println!("`{}`", safe_hash);
-
-    println!("\n## Links");
-    match tx_url {
-        Some(url) => println!("- [Proposal Transaction]({})", url),
-        None => println!("- Proposal Transaction NONE"),
+    for path in writer.write(report, tx_path)? {
+        println!("Saved report to: {}", path);
     }
-    println!("- [Simulation]({})", simulation_url);
-
     Ok(())
 }
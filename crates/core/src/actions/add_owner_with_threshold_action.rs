@@ -0,0 +1,41 @@
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct AddOwnerWithThreshold {
+    safe: Address,
+    owner: Address,
+    threshold: u64,
+}
+
+impl AddOwnerWithThreshold {
+    pub fn new(safe: Address, owner: Address, threshold: u64) -> Self {
+        Self {
+            safe,
+            owner,
+            threshold,
+        }
+    }
+}
+
+impl AdminAction for AddOwnerWithThreshold {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            GnosisSafe::addOwnerWithThresholdCall::new((self.owner, U256::from(self.threshold)))
+                .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "AddOwnerWithThreshold",
+            "safe": self.safe.to_string(),
+            "owner": self.owner.to_string(),
+            "threshold": self.threshold.to_string(),
+        })
+    }
+}
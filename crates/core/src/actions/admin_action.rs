@@ -1,5 +1,6 @@
+use crate::types::{selector_registry::SelectorRegistry, transaction::Operation};
 use alloy::primitives::{Address, Bytes, U256};
-use serde_json::Value;
+use serde_json::{Value, json};
 
 pub trait AdminAction {
     fn target(&self) -> Address;
@@ -7,5 +8,24 @@ pub trait AdminAction {
         U256::ZERO
     }
     fn data(&self) -> Bytes; // encode to tx data
+    fn operation(&self) -> Operation {
+        Operation::Call
+    }
     fn describe(&self) -> Value;
+    // Description with the signature of the called function looked up in the selector registry,
+    // so unknown selectors are flagged in reports.
+    fn describe_call(&self, selectors: &SelectorRegistry) -> Value {
+        let mut description = self.describe();
+        if let Some(function) = selectors.label_calldata(&self.data())
+            && let Some(fields) = description.as_object_mut()
+        {
+            fields.insert("function".to_string(), json!(function));
+        }
+        description
+    }
+    // Actions spending multisig funds must be executed by the multisig itself, never through a
+    // timelock.
+    fn requires_multisig_sender(&self) -> bool {
+        false
+    }
 }
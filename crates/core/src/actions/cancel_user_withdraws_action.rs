@@ -0,0 +1,49 @@
+use alloy::primitives::{Address, Bytes, keccak256};
+use alloy::sol_types::{SolCall, SolValue};
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction,
+    bindings::boring_queue::BoringOnChainQueue::{self, OnChainWithdraw},
+};
+
+pub struct CancelUserWithdraws {
+    queue: Address,
+    requests: Vec<OnChainWithdraw>,
+}
+
+impl CancelUserWithdraws {
+    pub fn new(queue: Address, requests: Vec<OnChainWithdraw>) -> Self {
+        Self { queue, requests }
+    }
+}
+
+impl AdminAction for CancelUserWithdraws {
+    fn target(&self) -> Address {
+        self.queue
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            BoringOnChainQueue::cancelUserWithdrawsCall::new((self.requests.clone(),)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        let requests: Vec<Value> = self
+            .requests
+            .iter()
+            .map(|r| {
+                json!({
+                    "request_id": keccak256(r.abi_encode()).to_string(),
+                    "user": r.user.to_string(),
+                    "asset_out": r.assetOut.to_string(),
+                    "amount_of_shares": r.amountOfShares.to_string(),
+                })
+            })
+            .collect();
+        json!({
+            "action": "CancelUserWithdraws",
+            "queue": self.queue.to_string(),
+            "requests": requests,
+        })
+    }
+}
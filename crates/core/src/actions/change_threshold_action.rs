@@ -0,0 +1,34 @@
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct ChangeThreshold {
+    safe: Address,
+    threshold: u64,
+}
+
+impl ChangeThreshold {
+    pub fn new(safe: Address, threshold: u64) -> Self {
+        Self { safe, threshold }
+    }
+}
+
+impl AdminAction for ChangeThreshold {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            GnosisSafe::changeThresholdCall::new((U256::from(self.threshold),)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "ChangeThreshold",
+            "safe": self.safe.to_string(),
+            "threshold": self.threshold.to_string(),
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct DisableModule {
+    safe: Address,
+    prev_module: Address,
+    module: Address,
+}
+
+impl DisableModule {
+    pub fn new(safe: Address, prev_module: Address, module: Address) -> Self {
+        Self {
+            safe,
+            prev_module,
+            module,
+        }
+    }
+}
+
+impl AdminAction for DisableModule {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            GnosisSafe::disableModuleCall::new((self.prev_module, self.module)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "DisableModule",
+            "safe": self.safe.to_string(),
+            "prev_module": self.prev_module.to_string(),
+            "module": self.module.to_string(),
+        })
+    }
+}
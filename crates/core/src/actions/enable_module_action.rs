@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct EnableModule {
+    safe: Address,
+    module: Address,
+}
+
+impl EnableModule {
+    pub fn new(safe: Address, module: Address) -> Self {
+        Self { safe, module }
+    }
+}
+
+impl AdminAction for EnableModule {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = GnosisSafe::enableModuleCall::new((self.module,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "EnableModule",
+            "safe": self.safe.to_string(),
+            "module": self.module.to_string(),
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use alloy::primitives::{Address, Bytes, U256, utils::format_units};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::erc20::ERC20};
+
+pub struct Erc20Approve {
+    token: Address,
+    spender: Address,
+    amount: U256,
+    symbol: String,
+    decimals: u8,
+}
+
+impl Erc20Approve {
+    pub fn new(
+        token: Address,
+        spender: Address,
+        amount: U256,
+        symbol: String,
+        decimals: u8,
+    ) -> Self {
+        Self {
+            token,
+            spender,
+            amount,
+            symbol,
+            decimals,
+        }
+    }
+}
+
+impl AdminAction for Erc20Approve {
+    fn target(&self) -> Address {
+        self.token
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = ERC20::approveCall::new((self.spender, self.amount)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        let formatted = format_units(self.amount, self.decimals).unwrap_or_default();
+        json!({
+            "action": "Erc20Approve",
+            "token": self.token.to_string(),
+            "spender": self.spender.to_string(),
+            "amount": format!("{} {}", formatted, self.symbol),
+            "raw_amount": self.amount.to_string(),
+        })
+    }
+    fn requires_multisig_sender(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,48 @@
+use alloy::primitives::{Address, Bytes, U256, utils::format_units};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::erc20::ERC20};
+
+pub struct Erc20Transfer {
+    token: Address,
+    to: Address,
+    amount: U256,
+    symbol: String,
+    decimals: u8,
+}
+
+impl Erc20Transfer {
+    pub fn new(token: Address, to: Address, amount: U256, symbol: String, decimals: u8) -> Self {
+        Self {
+            token,
+            to,
+            amount,
+            symbol,
+            decimals,
+        }
+    }
+}
+
+impl AdminAction for Erc20Transfer {
+    fn target(&self) -> Address {
+        self.token
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = ERC20::transferCall::new((self.to, self.amount)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        let formatted = format_units(self.amount, self.decimals).unwrap_or_default();
+        json!({
+            "action": "Erc20Transfer",
+            "token": self.token.to_string(),
+            "to": self.to.to_string(),
+            "amount": format!("{} {}", formatted, self.symbol),
+            "raw_amount": self.amount.to_string(),
+        })
+    }
+    fn requires_multisig_sender(&self) -> bool {
+        true
+    }
+}
@@ -1,15 +1,48 @@
 pub mod add_asset_action;
+pub mod add_owner_with_threshold_action;
 pub mod admin_action;
+pub mod cancel_user_withdraws_action;
+pub mod change_threshold_action;
+pub mod disable_module_action;
+pub mod enable_module_action;
+pub mod erc20_approve_action;
+pub mod erc20_transfer_action;
 pub mod multisend_utils;
+pub mod native_transfer_action;
+pub mod pause_accountant_action;
+pub mod pause_manager_action;
+pub mod pause_queue_action;
+pub mod pause_teller_action;
+pub mod raw_call_action;
 pub mod remove_asset_action;
+pub mod remove_owner_action;
+pub mod set_authority_action;
+pub mod set_before_transfer_hook_action;
+pub mod set_deposit_cap_action;
+pub mod set_guard_action;
 pub mod set_merkle_root_action;
 pub mod set_public_capability_action;
 pub mod set_rate_provider_data_action;
 pub mod set_role_capability_action;
+pub mod set_share_lock_period_action;
 pub mod set_user_role_action;
+pub mod set_withdraw_capacity_action;
+pub mod sign_message_action;
 pub mod stop_withdraws_in_asset_action;
+pub mod swap_owner_action;
 pub mod timelock_action;
+pub mod transfer_ownership_action;
+pub mod unpause_accountant_action;
+pub mod unpause_queue_action;
+pub mod unpause_teller_action;
 pub mod update_asset_data_action;
+pub mod update_delay_action;
+pub mod update_exchange_rate_action;
+pub mod update_lower_action;
+pub mod update_management_fee_action;
+pub mod update_payout_address_action;
 pub mod update_performance_fee_action;
 pub mod update_platform_fee_action;
+pub mod update_teller_deny_list_action;
+pub mod update_upper_action;
 pub mod update_withdraw_asset_action;
@@ -1,30 +1,213 @@
-use crate::bindings::multisend::MutliSendCallOnly;
-use crate::types::transaction::Transaction;
-use alloy::primitives::{Bytes, U256};
+use crate::bindings::multisend::{MultiSend, MutliSendCallOnly};
+use crate::types::{
+    batch_limits::BatchLimits,
+    config_wrapper::ConfigWrapper,
+    simulation_config::SimulationConfig,
+    transaction::{Operation, Transaction},
+};
+use alloy::primitives::{Address, Bytes, U256};
 use alloy::sol_types::SolCall;
+use eyre::{Result, eyre};
+use std::ops::Range;
 
 pub fn create_multisend_data(txs: Vec<Transaction>) -> Bytes {
     let mut encoded_transactions = Vec::new();
-    for i in 0..txs.len() {
-        // operation (0 for Call) - 1 byte
-        encoded_transactions.push(0u8);
+    for tx in &txs {
+        // operation (0 for Call, 1 for DelegateCall) - 1 byte
+        encoded_transactions.push(tx.operation.as_u8());
 
         // to address - 20 bytes
-        encoded_transactions.extend_from_slice(&txs[i].to.as_slice());
+        encoded_transactions.extend_from_slice(tx.to.as_slice());
 
         // value - 32 bytes
-        encoded_transactions.extend_from_slice(&txs[i].value.to_be_bytes::<32>());
+        encoded_transactions.extend_from_slice(&tx.value.to_be_bytes::<32>());
 
         // data length - 32 bytes
-        let data_len = U256::from(txs[i].data.len());
+        let data_len = U256::from(tx.data.len());
         encoded_transactions.extend_from_slice(&data_len.to_be_bytes::<32>());
 
         // data - dynamic length
-        encoded_transactions.extend_from_slice(&txs[i].data);
+        encoded_transactions.extend_from_slice(&tx.data);
     }
 
-    let multisend_data =
-        MutliSendCallOnly::multiSendCall::new((Bytes::from(encoded_transactions),)).abi_encode();
+    // Both MultiSend versions share the same function, so the selector is identical.
+    let multisend_data = if txs.iter().any(|tx| tx.operation == Operation::DelegateCall) {
+        MultiSend::multiSendCall::new((Bytes::from(encoded_transactions),)).abi_encode()
+    } else {
+        MutliSendCallOnly::multiSendCall::new((Bytes::from(encoded_transactions),)).abi_encode()
+    };
 
     Bytes::from(multisend_data)
 }
+
+// Builds the safe tx for a list of txs, making the call directly if there is only one, otherwise
+// batching them through MultiSendCallOnly, or the full MultiSend if any tx is a delegatecall.
+pub fn build_multisig_tx_config(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    multisig: Address,
+    nonce: u32,
+    txs: Vec<Transaction>,
+) -> Result<SimulationConfig> {
+    // Refuse delegatecalls into anything that is not explicitly allowed.
+    let allowlist = cw.get_delegate_call_allowlist(network_id)?;
+    for tx in &txs {
+        if tx.operation == Operation::DelegateCall && !allowlist.contains(&tx.to) {
+            return Err(eyre!(
+                "Delegatecall to {} on network {} is not in delegate_call_allowlist",
+                tx.to,
+                network_id
+            ));
+        }
+    }
+
+    match txs.len() {
+        0 => Err(eyre!(
+            "No transactions to send for multisig {} on network {}",
+            multisig,
+            network_id
+        )),
+        1 => Ok(SimulationConfig {
+            network_id,
+            multisig: multisig.to_string(),
+            to: txs[0].to.to_string(),
+            value: txs[0].value.to_string(),
+            data: format!("0x{}", hex::encode(txs[0].data.clone())),
+            operation: txs[0].operation.as_u8(),
+            nonce,
+        }),
+        _ => {
+            let needs_delegate_call = txs.iter().any(|tx| tx.operation == Operation::DelegateCall);
+            let multisend_addr: Address = if needs_delegate_call {
+                cw.get_multisend_full_address(network_id)?.parse()?
+            } else {
+                cw.get_multisend_address(network_id)?.parse()?
+            };
+            let data = create_multisend_data(txs);
+            Ok(SimulationConfig {
+                network_id,
+                multisig: multisig.to_string(),
+                to: multisend_addr.to_string(),
+                value: "0".to_string(),
+                data: format!("0x{}", hex::encode(data)),
+                operation: Operation::DelegateCall.as_u8(),
+                nonce,
+            })
+        }
+    }
+}
+
+// Size of the calldata the multisig executes for a list of txs, either the data of the only tx
+// or the abi encoded multiSend call.
+pub fn multisig_tx_calldata_len(txs: &[Transaction]) -> usize {
+    match txs.len() {
+        0 => 0,
+        1 => txs[0].data.len(),
+        _ => {
+            // operation + to + value + data length + data for every tx.
+            let packed: usize = txs.iter().map(|tx| 1 + 20 + 32 + 32 + tx.data.len()).sum();
+            // selector + offset + length + padded packed transactions.
+            4 + 32 + 32 + packed.div_ceil(32) * 32
+        }
+    }
+}
+
+// Splits txs into consecutive chunks that each fit within the limits, keeping their order.
+// `gas_used` must hold one estimate per tx if a gas limit is configured.
+pub fn split_txs_by_limits(
+    txs: &[Transaction],
+    gas_used: Option<&[u64]>,
+    limits: &BatchLimits,
+) -> Result<Vec<Range<usize>>> {
+    let fits = |range: &Range<usize>| -> bool {
+        let calldata_ok = limits
+            .max_calldata_bytes
+            .is_none_or(|max| multisig_tx_calldata_len(&txs[range.clone()]) <= max);
+        let gas_ok = match (limits.max_gas, gas_used) {
+            (Some(max), Some(gas_used)) => gas_used[range.clone()].iter().sum::<u64>() <= max,
+            _ => true,
+        };
+        calldata_ok && gas_ok
+    };
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < txs.len() {
+        if !fits(&(start..start + 1)) {
+            return Err(eyre!(
+                "Transaction {} to {} exceeds the batch limits on its own and can not be split",
+                start,
+                txs[start].to
+            ));
+        }
+        let mut end = start + 1;
+        while end < txs.len() && fits(&(start..end + 1)) {
+            end += 1;
+        }
+        chunks.push(start..end);
+        start = end;
+    }
+
+    Ok(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(data_len: usize) -> Transaction {
+        Transaction {
+            to: Address::ZERO,
+            value: U256::ZERO,
+            data: Bytes::from(vec![0u8; data_len]),
+            operation: Operation::Call,
+        }
+    }
+
+    #[test]
+    fn keeps_everything_in_one_chunk_without_limits() {
+        let txs = vec![tx(100), tx(200), tx(300)];
+        let chunks = split_txs_by_limits(&txs, None, &BatchLimits::default()).unwrap();
+        assert_eq!(chunks, vec![0..3]);
+    }
+
+    #[test]
+    fn splits_by_gas_in_order() {
+        let txs = vec![tx(4), tx(4), tx(4), tx(4)];
+        let limits = BatchLimits {
+            max_gas: Some(100),
+            max_calldata_bytes: None,
+        };
+        let chunks = split_txs_by_limits(&txs, Some(&[60, 40, 70, 20]), &limits).unwrap();
+        assert_eq!(chunks, vec![0..2, 2..4]);
+    }
+
+    #[test]
+    fn splits_by_multisend_calldata_size() {
+        let txs = vec![tx(100), tx(100), tx(100)];
+        // Two txs take 4 + 32 + 32 + 2 * 185 bytes rounded up to 32 bytes.
+        let limits = BatchLimits {
+            max_gas: None,
+            max_calldata_bytes: Some(4 + 32 + 32 + 384),
+        };
+        let chunks = split_txs_by_limits(&txs, None, &limits).unwrap();
+        assert_eq!(chunks, vec![0..2, 2..3]);
+    }
+
+    #[test]
+    fn rejects_txs_over_the_limits_on_their_own() {
+        let limits = BatchLimits {
+            max_gas: Some(100),
+            max_calldata_bytes: Some(50),
+        };
+        assert!(split_txs_by_limits(&[tx(4), tx(60)], Some(&[1, 1]), &limits).is_err());
+        assert!(split_txs_by_limits(&[tx(4)], Some(&[101]), &limits).is_err());
+    }
+
+    #[test]
+    fn calldata_of_a_single_tx_is_its_own_data() {
+        assert_eq!(multisig_tx_calldata_len(&[]), 0);
+        assert_eq!(multisig_tx_calldata_len(&[tx(68)]), 68);
+        assert_eq!(multisig_tx_calldata_len(&[tx(0), tx(0)]), 4 + 32 + 32 + 192);
+    }
+}
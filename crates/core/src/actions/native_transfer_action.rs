@@ -0,0 +1,39 @@
+use alloy::primitives::{Address, Bytes, U256, utils::format_ether};
+use serde_json::{Value, json};
+
+use crate::actions::admin_action::AdminAction;
+
+pub struct NativeTransfer {
+    to: Address,
+    amount: U256,
+    symbol: String,
+}
+
+impl NativeTransfer {
+    pub fn new(to: Address, amount: U256, symbol: String) -> Self {
+        Self { to, amount, symbol }
+    }
+}
+
+impl AdminAction for NativeTransfer {
+    fn target(&self) -> Address {
+        self.to
+    }
+    fn value(&self) -> U256 {
+        self.amount
+    }
+    fn data(&self) -> Bytes {
+        Bytes::new()
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "NativeTransfer",
+            "to": self.to.to_string(),
+            "amount": format!("{} {}", format_ether(self.amount), self.symbol),
+            "raw_amount": self.amount.to_string(),
+        })
+    }
+    fn requires_multisig_sender(&self) -> bool {
+        true
+    }
+}
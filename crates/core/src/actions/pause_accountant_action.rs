@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct PauseAccountant {
+    accountant: Address,
+}
+
+impl PauseAccountant {
+    pub fn new(accountant: Address) -> Self {
+        Self { accountant }
+    }
+}
+
+impl AdminAction for PauseAccountant {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = AccountantWithRateProviders::pauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "PauseAccountant",
+            "accountant": self.accountant.to_string(),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::manager::ManagerWithMerkleVerification};
+
+pub struct PauseManager {
+    manager: Address,
+}
+
+impl PauseManager {
+    pub fn new(manager: Address) -> Self {
+        Self { manager }
+    }
+}
+
+impl AdminAction for PauseManager {
+    fn target(&self) -> Address {
+        self.manager
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = ManagerWithMerkleVerification::pauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "PauseManager",
+            "manager": self.manager.to_string(),
+        })
+    }
+}
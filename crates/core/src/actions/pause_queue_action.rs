@@ -0,0 +1,31 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::boring_queue::BoringOnChainQueue};
+
+pub struct PauseQueue {
+    queue: Address,
+}
+
+impl PauseQueue {
+    pub fn new(queue: Address) -> Self {
+        Self { queue }
+    }
+}
+
+impl AdminAction for PauseQueue {
+    fn target(&self) -> Address {
+        self.queue
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = BoringOnChainQueue::pauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "PauseQueue",
+            "queue": self.queue.to_string(),
+        })
+    }
+}
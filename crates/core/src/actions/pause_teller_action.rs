@@ -0,0 +1,31 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::teller::TellerWithMultiAssetSupport};
+
+pub struct PauseTeller {
+    teller: Address,
+}
+
+impl PauseTeller {
+    pub fn new(teller: Address) -> Self {
+        Self { teller }
+    }
+}
+
+impl AdminAction for PauseTeller {
+    fn target(&self) -> Address {
+        self.teller
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = TellerWithMultiAssetSupport::pauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "PauseTeller",
+            "teller": self.teller.to_string(),
+        })
+    }
+}
@@ -0,0 +1,76 @@
+use alloy::primitives::{Address, Bytes, U256};
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction,
+    types::{selector_registry::SelectorRegistry, transaction::Operation},
+};
+
+/// Call encoded from a human readable function signature, for one off calls with no processor.
+pub struct RawCall {
+    target: Address,
+    value: U256,
+    data: Bytes,
+    operation: Operation,
+    signature: String,
+    args: Vec<Value>,
+    direct: bool,
+}
+
+impl RawCall {
+    pub fn new(
+        target: Address,
+        value: U256,
+        data: Bytes,
+        operation: Operation,
+        signature: String,
+        args: Vec<Value>,
+        direct: bool,
+    ) -> Self {
+        Self {
+            target,
+            value,
+            data,
+            operation,
+            signature,
+            args,
+            direct,
+        }
+    }
+}
+
+impl AdminAction for RawCall {
+    fn target(&self) -> Address {
+        self.target
+    }
+    fn value(&self) -> U256 {
+        self.value
+    }
+    fn data(&self) -> Bytes {
+        self.data.clone()
+    }
+    fn operation(&self) -> Operation {
+        self.operation
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "RawCall",
+            "target": self.target.to_string(),
+            "signature": self.signature,
+            "args": self.args,
+            "value": self.value.to_string(),
+            "operation": format!("{:?}", self.operation),
+            "direct": self.direct,
+        })
+    }
+    // Raw calls carry their own signature, which need not be in the selector registry.
+    fn describe_call(&self, _selectors: &SelectorRegistry) -> Value {
+        let mut description = self.describe();
+        description["function"] = json!(self.signature);
+        description
+    }
+    // Calls sending value spend the funds of the multisig, the timelock holds none.
+    fn requires_multisig_sender(&self) -> bool {
+        self.direct || !self.value.is_zero()
+    }
+}
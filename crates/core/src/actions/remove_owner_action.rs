@@ -0,0 +1,47 @@
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct RemoveOwner {
+    safe: Address,
+    prev_owner: Address,
+    owner: Address,
+    threshold: u64,
+}
+
+impl RemoveOwner {
+    pub fn new(safe: Address, prev_owner: Address, owner: Address, threshold: u64) -> Self {
+        Self {
+            safe,
+            prev_owner,
+            owner,
+            threshold,
+        }
+    }
+}
+
+impl AdminAction for RemoveOwner {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = GnosisSafe::removeOwnerCall::new((
+            self.prev_owner,
+            self.owner,
+            U256::from(self.threshold),
+        ))
+        .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "RemoveOwner",
+            "safe": self.safe.to_string(),
+            "prev_owner": self.prev_owner.to_string(),
+            "owner": self.owner.to_string(),
+            "threshold": self.threshold.to_string(),
+        })
+    }
+}
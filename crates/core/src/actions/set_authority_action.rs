@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::auth::Auth};
+
+pub struct SetAuthority {
+    target: Address,
+    authority: Address,
+}
+
+impl SetAuthority {
+    pub fn new(target: Address, authority: Address) -> Self {
+        Self { target, authority }
+    }
+}
+
+impl AdminAction for SetAuthority {
+    fn target(&self) -> Address {
+        self.target
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = Auth::setAuthorityCall::new((self.authority,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SetAuthority",
+            "target": self.target.to_string(),
+            "authority": self.authority.to_string(),
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::boring_vault::BoringVault};
+
+pub struct SetBeforeTransferHook {
+    vault: Address,
+    hook: Address,
+}
+
+impl SetBeforeTransferHook {
+    pub fn new(vault: Address, hook: Address) -> Self {
+        Self { vault, hook }
+    }
+}
+
+impl AdminAction for SetBeforeTransferHook {
+    fn target(&self) -> Address {
+        self.vault
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = BoringVault::setBeforeTransferHookCall::new((self.hook,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SetBeforeTransferHook",
+            "vault": self.vault.to_string(),
+            "hook": self.hook.to_string(),
+        })
+    }
+}
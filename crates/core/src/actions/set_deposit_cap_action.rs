@@ -0,0 +1,35 @@
+use alloy::primitives::{Address, Bytes, aliases::U112};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::teller::TellerWithMultiAssetSupport};
+
+pub struct SetDepositCap {
+    teller: Address,
+    cap: u128,
+}
+
+impl SetDepositCap {
+    pub fn new(teller: Address, cap: u128) -> Self {
+        Self { teller, cap }
+    }
+}
+
+impl AdminAction for SetDepositCap {
+    fn target(&self) -> Address {
+        self.teller
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            TellerWithMultiAssetSupport::setDepositCapCall::new((U112::from(self.cap),))
+                .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SetDepositCap",
+            "teller": self.teller.to_string(),
+            "cap": self.cap.to_string(),
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct SetGuard {
+    safe: Address,
+    guard: Address,
+}
+
+impl SetGuard {
+    pub fn new(safe: Address, guard: Address) -> Self {
+        Self { safe, guard }
+    }
+}
+
+impl AdminAction for SetGuard {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = GnosisSafe::setGuardCall::new((self.guard,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SetGuard",
+            "safe": self.safe.to_string(),
+            "guard": self.guard.to_string(),
+        })
+    }
+}
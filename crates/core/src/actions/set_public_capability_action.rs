@@ -20,7 +20,7 @@ impl SetPublicCapabilityAction {
         enabled: bool,
     ) -> Self {
         let function_selector =
-            FixedBytes::<4>::from_slice(&keccak256(function_signature.as_bytes())[..4].to_vec());
+            FixedBytes::<4>::from_slice(&keccak256(function_signature.as_bytes())[..4]);
         Self {
             roles_authority,
             target,
@@ -32,6 +32,7 @@ impl SetPublicCapabilityAction {
 }
 
 impl AdminAction for SetPublicCapabilityAction {
+    #[allow(clippy::misnamed_getters)]
     fn target(&self) -> Address {
         self.roles_authority
     }
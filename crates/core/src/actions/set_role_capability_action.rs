@@ -2,11 +2,14 @@ use alloy::primitives::{Address, Bytes, FixedBytes, aliases::B32, keccak256};
 use alloy::sol_types::SolCall;
 use serde_json::{Value, json};
 
-use crate::{actions::admin_action::AdminAction, bindings::roles_authority::RolesAuthority};
+use crate::{
+    actions::admin_action::AdminAction, bindings::roles_authority::RolesAuthority,
+    types::role_registry::Role,
+};
 
 pub struct SetRoleCapabilityAction {
     roles_authority: Address,
-    role: u8,
+    role: Role,
     target: Address,
     function_signature: String,
     function_selector: B32,
@@ -16,13 +19,13 @@ pub struct SetRoleCapabilityAction {
 impl SetRoleCapabilityAction {
     pub fn new(
         roles_authority: Address,
-        role: u8,
+        role: Role,
         target: Address,
         function_signature: String,
         enabled: bool,
     ) -> Self {
         let function_selector =
-            FixedBytes::<4>::from_slice(&keccak256(function_signature.as_bytes())[..4].to_vec());
+            FixedBytes::<4>::from_slice(&keccak256(function_signature.as_bytes())[..4]);
         Self {
             roles_authority,
             role,
@@ -35,12 +38,13 @@ impl SetRoleCapabilityAction {
 }
 
 impl AdminAction for SetRoleCapabilityAction {
+    #[allow(clippy::misnamed_getters)]
     fn target(&self) -> Address {
         self.roles_authority
     }
     fn data(&self) -> Bytes {
         let bytes_data = RolesAuthority::setRoleCapabilityCall::new((
-            self.role,
+            self.role.id,
             self.target,
             self.function_selector,
             self.enabled,
@@ -0,0 +1,38 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::teller::TellerWithMultiAssetSupport};
+
+pub struct SetShareLockPeriod {
+    teller: Address,
+    share_lock_period: u64,
+}
+
+impl SetShareLockPeriod {
+    pub fn new(teller: Address, share_lock_period: u64) -> Self {
+        Self {
+            teller,
+            share_lock_period,
+        }
+    }
+}
+
+impl AdminAction for SetShareLockPeriod {
+    fn target(&self) -> Address {
+        self.teller
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            TellerWithMultiAssetSupport::setShareLockPeriodCall::new((self.share_lock_period,))
+                .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SetShareLockPeriod",
+            "teller": self.teller.to_string(),
+            "share_lock_period": self.share_lock_period.to_string(),
+        })
+    }
+}
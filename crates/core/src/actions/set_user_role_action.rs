@@ -2,17 +2,20 @@ use alloy::primitives::{Address, Bytes};
 use alloy::sol_types::SolCall;
 use serde_json::{Value, json};
 
-use crate::{actions::admin_action::AdminAction, bindings::roles_authority::RolesAuthority};
+use crate::{
+    actions::admin_action::AdminAction, bindings::roles_authority::RolesAuthority,
+    types::role_registry::Role,
+};
 
 pub struct SetUserRoleAction {
     roles_authority: Address,
     user: Address,
-    role: u8,
+    role: Role,
     enabled: bool,
 }
 
 impl SetUserRoleAction {
-    pub fn new(roles_authority: Address, user: Address, role: u8, enabled: bool) -> Self {
+    pub fn new(roles_authority: Address, user: Address, role: Role, enabled: bool) -> Self {
         Self {
             roles_authority,
             user,
@@ -28,7 +31,8 @@ impl AdminAction for SetUserRoleAction {
     }
     fn data(&self) -> Bytes {
         let bytes_data =
-            RolesAuthority::setUserRoleCall::new((self.user, self.role, self.enabled)).abi_encode();
+            RolesAuthority::setUserRoleCall::new((self.user, self.role.id, self.enabled))
+                .abi_encode();
         Bytes::from(bytes_data)
     }
     fn describe(&self) -> Value {
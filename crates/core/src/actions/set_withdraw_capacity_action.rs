@@ -0,0 +1,41 @@
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::boring_queue::BoringOnChainQueue};
+
+pub struct SetWithdrawCapacity {
+    queue: Address,
+    asset: Address,
+    withdraw_capacity: U256,
+}
+
+impl SetWithdrawCapacity {
+    pub fn new(queue: Address, asset: Address, withdraw_capacity: U256) -> Self {
+        Self {
+            queue,
+            asset,
+            withdraw_capacity,
+        }
+    }
+}
+
+impl AdminAction for SetWithdrawCapacity {
+    fn target(&self) -> Address {
+        self.queue
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            BoringOnChainQueue::setWithdrawCapacityCall::new((self.asset, self.withdraw_capacity))
+                .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SetWithdrawCapacity",
+            "queue": self.queue.to_string(),
+            "asset": self.asset.to_string(),
+            "withdraw_capacity": self.withdraw_capacity.to_string(),
+        })
+    }
+}
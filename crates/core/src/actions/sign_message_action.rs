@@ -0,0 +1,43 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::sign_message_lib::SignMessageLib,
+    types::transaction::Operation,
+};
+
+pub struct SignMessage {
+    sign_message_lib: Address,
+    message: Bytes,
+}
+
+impl SignMessage {
+    pub fn new(sign_message_lib: Address, message: Bytes) -> Self {
+        Self {
+            sign_message_lib,
+            message,
+        }
+    }
+}
+
+impl AdminAction for SignMessage {
+    fn target(&self) -> Address {
+        self.sign_message_lib
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = SignMessageLib::signMessageCall::new((self.message.clone(),)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn operation(&self) -> Operation {
+        Operation::DelegateCall
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SignMessage",
+            "sign_message_lib": self.sign_message_lib.to_string(),
+            "message": self.message.to_string(),
+            "operation": "DelegateCall",
+        })
+    }
+}
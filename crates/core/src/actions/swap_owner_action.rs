@@ -0,0 +1,44 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::multisig::GnosisSafe};
+
+pub struct SwapOwner {
+    safe: Address,
+    prev_owner: Address,
+    old_owner: Address,
+    new_owner: Address,
+}
+
+impl SwapOwner {
+    pub fn new(safe: Address, prev_owner: Address, old_owner: Address, new_owner: Address) -> Self {
+        Self {
+            safe,
+            prev_owner,
+            old_owner,
+            new_owner,
+        }
+    }
+}
+
+impl AdminAction for SwapOwner {
+    fn target(&self) -> Address {
+        self.safe
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            GnosisSafe::swapOwnerCall::new((self.prev_owner, self.old_owner, self.new_owner))
+                .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "SwapOwner",
+            "safe": self.safe.to_string(),
+            "prev_owner": self.prev_owner.to_string(),
+            "old_owner": self.old_owner.to_string(),
+            "new_owner": self.new_owner.to_string(),
+        })
+    }
+}
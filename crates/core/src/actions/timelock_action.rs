@@ -1,9 +1,13 @@
-use alloy::primitives::{Address, B256, Bytes, U256};
-use alloy::sol_types::SolCall;
+use alloy::primitives::{Address, B256, Bytes, U256, keccak256};
+use alloy::sol_types::{SolCall, SolValue};
 use serde_json::{Value, json};
 
-use crate::{actions::admin_action::AdminAction, bindings::timelock::Timelock};
+use crate::{
+    actions::admin_action::AdminAction, bindings::timelock::Timelock,
+    types::selector_registry::SelectorRegistry,
+};
 
+#[derive(Clone, Copy)]
 enum Mode {
     Propose,
     Execute,
@@ -17,6 +21,8 @@ pub struct TimelockAction {
     timelock: Address,
     delay: U256,
     actions: Vec<Box<dyn AdminAction>>,
+    predecessor: B256,
+    salt: B256,
 }
 
 impl TimelockAction {
@@ -27,6 +33,70 @@ impl TimelockAction {
             timelock,
             delay,
             actions,
+            predecessor: DEFAULT_PREDECESSOR,
+            salt: DEFAULT_SALT,
+        }
+    }
+
+    /// Sets the operation this one can only be executed after, and the salt telling apart
+    /// operations with the same calls.
+    pub fn chain(&mut self, predecessor: B256, salt: B256) {
+        self.predecessor = predecessor;
+        self.salt = salt;
+    }
+
+    /// Id of the operation in the timelock, as computed by `hashOperationBatch`.
+    pub fn operation_id(&self) -> B256 {
+        let (targets, values, data) = self.calls();
+        keccak256((targets, values, data, self.predecessor, self.salt).abi_encode_params())
+    }
+
+    // Targets, values and calldata of the inner actions.
+    fn calls(&self) -> (Vec<Address>, Vec<U256>, Vec<Bytes>) {
+        (
+            self.actions.iter().map(|action| action.target()).collect(),
+            self.actions.iter().map(|action| action.value()).collect(),
+            self.actions.iter().map(|action| action.data()).collect(),
+        )
+    }
+
+    // Description with every inner action described by `describe`.
+    fn describe_with(&self, describe: impl Fn(&dyn AdminAction) -> Value) -> Value {
+        let action = match self.mode {
+            Mode::Propose => "ProposeBatch",
+            Mode::Execute => "ExecuteBatch",
+        };
+        json!({
+            "action": action,
+            "timelock": self.timelock.to_string(),
+            "operation_id": self.operation_id().to_string(),
+            "predecessor": self.predecessor.to_string(),
+            "inner": self.actions.iter().map(|action| describe(action.as_ref())).collect::<Vec<_>>()
+        })
+    }
+
+    pub fn action_count(&self) -> usize {
+        self.actions.len()
+    }
+
+    pub fn push(&mut self, action: Box<dyn AdminAction>) {
+        self.actions.push(action);
+    }
+
+    pub fn pop(&mut self) -> Option<Box<dyn AdminAction>> {
+        self.actions.pop()
+    }
+
+    /// Moves the inner actions from `at` onwards into a new timelock action with the same
+    /// timelock, delay and mode.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        Self {
+            mode: self.mode,
+            timelock: self.timelock,
+            delay: self.delay,
+            actions: self.actions.split_off(at),
+            predecessor: DEFAULT_PREDECESSOR,
+            salt: DEFAULT_SALT,
         }
     }
 
@@ -44,28 +114,14 @@ impl AdminAction for TimelockAction {
     }
 
     fn data(&self) -> Bytes {
-        let targets = self
-            .actions
-            .iter()
-            .map(|action| action.target())
-            .collect::<Vec<_>>();
-        let values = self
-            .actions
-            .iter()
-            .map(|action| action.value())
-            .collect::<Vec<_>>();
-        let data = self
-            .actions
-            .iter()
-            .map(|action| action.data())
-            .collect::<Vec<_>>();
+        let (targets, values, data) = self.calls();
         let tx_data = match self.mode {
             Mode::Propose => Timelock::scheduleBatchCall::new((
                 targets,
                 values,
                 data,
-                DEFAULT_PREDECESSOR,
-                DEFAULT_SALT,
+                self.predecessor,
+                self.salt,
                 self.delay,
             ))
             .abi_encode(),
@@ -73,8 +129,8 @@ impl AdminAction for TimelockAction {
                 targets,
                 values,
                 data,
-                DEFAULT_PREDECESSOR,
-                DEFAULT_SALT,
+                self.predecessor,
+                self.salt,
             ))
             .abi_encode(),
         };
@@ -83,21 +139,14 @@ impl AdminAction for TimelockAction {
     }
 
     fn describe(&self) -> Value {
-        match self.mode {
-            Mode::Propose => {
-                json!({
-                    "action": "ProposeBatch",
-                    "timelock": self.timelock.to_string(),
-                    "inner": self.actions.iter().map(|action| action.describe()).collect::<Vec<_>>()
-                })
-            }
-            Mode::Execute => {
-                json!({
-                    "action": "ExecuteBatch",
-                    "timelock": self.timelock.to_string(),
-                    "inner": self.actions.iter().map(|action| action.describe()).collect::<Vec<_>>()
-                })
-            }
+        self.describe_with(|action| action.describe())
+    }
+
+    fn describe_call(&self, selectors: &SelectorRegistry) -> Value {
+        let mut description = self.describe_with(|action| action.describe_call(selectors));
+        if let Some(function) = selectors.label_calldata(&self.data()) {
+            description["function"] = json!(function);
         }
+        description
     }
 }
@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::auth::Auth};
+
+pub struct TransferOwnership {
+    target: Address,
+    new_owner: Address,
+}
+
+impl TransferOwnership {
+    pub fn new(target: Address, new_owner: Address) -> Self {
+        Self { target, new_owner }
+    }
+}
+
+impl AdminAction for TransferOwnership {
+    fn target(&self) -> Address {
+        self.target
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = Auth::transferOwnershipCall::new((self.new_owner,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "TransferOwnership",
+            "target": self.target.to_string(),
+            "new_owner": self.new_owner.to_string(),
+        })
+    }
+}
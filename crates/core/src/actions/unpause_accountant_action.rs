@@ -0,0 +1,33 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct UnpauseAccountant {
+    accountant: Address,
+}
+
+impl UnpauseAccountant {
+    pub fn new(accountant: Address) -> Self {
+        Self { accountant }
+    }
+}
+
+impl AdminAction for UnpauseAccountant {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = AccountantWithRateProviders::unpauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UnpauseAccountant",
+            "accountant": self.accountant.to_string(),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::boring_queue::BoringOnChainQueue};
+
+pub struct UnpauseQueue {
+    queue: Address,
+}
+
+impl UnpauseQueue {
+    pub fn new(queue: Address) -> Self {
+        Self { queue }
+    }
+}
+
+impl AdminAction for UnpauseQueue {
+    fn target(&self) -> Address {
+        self.queue
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = BoringOnChainQueue::unpauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UnpauseQueue",
+            "queue": self.queue.to_string(),
+        })
+    }
+}
@@ -0,0 +1,31 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::teller::TellerWithMultiAssetSupport};
+
+pub struct UnpauseTeller {
+    teller: Address,
+}
+
+impl UnpauseTeller {
+    pub fn new(teller: Address) -> Self {
+        Self { teller }
+    }
+}
+
+impl AdminAction for UnpauseTeller {
+    fn target(&self) -> Address {
+        self.teller
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = TellerWithMultiAssetSupport::unpauseCall::new(()).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UnpauseTeller",
+            "teller": self.teller.to_string(),
+        })
+    }
+}
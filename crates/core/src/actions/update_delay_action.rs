@@ -0,0 +1,51 @@
+use alloy::primitives::{Address, Bytes, aliases::U24};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction,
+    bindings::accountant::{AccountantWithRateProviders, LegacyAccountantWithRateProviders},
+    types::contract_versions::AccountantVersion,
+};
+
+pub struct UpdateDelay {
+    accountant: Address,
+    delay: u32,
+    version: AccountantVersion,
+}
+
+impl UpdateDelay {
+    // The delay must fit in a uint24 for V2 accountants, checked by the processor.
+    pub fn new(accountant: Address, delay: u32, version: AccountantVersion) -> Self {
+        Self {
+            accountant,
+            delay,
+            version,
+        }
+    }
+}
+
+impl AdminAction for UpdateDelay {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = match self.version {
+            AccountantVersion::V2 => {
+                AccountantWithRateProviders::updateDelayCall::new((U24::from(self.delay),))
+                    .abi_encode()
+            }
+            AccountantVersion::V1 => {
+                LegacyAccountantWithRateProviders::updateDelayCall::new((self.delay,)).abi_encode()
+            }
+        };
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UpdateDelay",
+            "accountant": self.accountant.to_string(),
+            "delay": self.delay.to_string(),
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use alloy::primitives::{Address, Bytes, aliases::U96};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct UpdateExchangeRate {
+    accountant: Address,
+    current_exchange_rate: u128,
+    new_exchange_rate: u128,
+}
+
+impl UpdateExchangeRate {
+    pub fn new(accountant: Address, current_exchange_rate: u128, new_exchange_rate: u128) -> Self {
+        Self {
+            accountant,
+            current_exchange_rate,
+            new_exchange_rate,
+        }
+    }
+}
+
+impl AdminAction for UpdateExchangeRate {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data = AccountantWithRateProviders::updateExchangeRateCall::new((U96::from(
+            self.new_exchange_rate,
+        ),))
+        .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UpdateExchangeRate",
+            "accountant": self.accountant.to_string(),
+            "current_exchange_rate": self.current_exchange_rate.to_string(),
+            "new_exchange_rate": self.new_exchange_rate.to_string(),
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct UpdateLower {
+    accountant: Address,
+    lower: u16,
+}
+
+impl UpdateLower {
+    pub fn new(accountant: Address, lower: u16) -> Self {
+        Self { accountant, lower }
+    }
+}
+
+impl AdminAction for UpdateLower {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            AccountantWithRateProviders::updateLowerCall::new((self.lower,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UpdateLower",
+            "accountant": self.accountant.to_string(),
+            "lower": self.lower.to_string(),
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct UpdateManagementFee {
+    accountant: Address,
+    fee: u16,
+}
+
+impl UpdateManagementFee {
+    pub fn new(accountant: Address, fee: u16) -> Self {
+        Self { accountant, fee }
+    }
+}
+
+impl AdminAction for UpdateManagementFee {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            AccountantWithRateProviders::updateManagementFeeCall::new((self.fee,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UpdateManagementFee",
+            "accountant": self.accountant.to_string(),
+            "fee": self.fee.to_string(),
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct UpdatePayoutAddress {
+    accountant: Address,
+    payout_address: Address,
+}
+
+impl UpdatePayoutAddress {
+    pub fn new(accountant: Address, payout_address: Address) -> Self {
+        Self {
+            accountant,
+            payout_address,
+        }
+    }
+}
+
+impl AdminAction for UpdatePayoutAddress {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            AccountantWithRateProviders::updatePayoutAddressCall::new((self.payout_address,))
+                .abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UpdatePayoutAddress",
+            "accountant": self.accountant.to_string(),
+            "payout_address": self.payout_address.to_string(),
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{actions::admin_action::AdminAction, bindings::teller::TellerWithMultiAssetSupport};
+
+/// Teller transfer deny lists, checked against the share sender, receiver and operator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DenyList {
+    From,
+    To,
+    Operator,
+}
+
+impl DenyList {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DenyList::From => "from",
+            DenyList::To => "to",
+            DenyList::Operator => "operator",
+        }
+    }
+}
+
+pub struct UpdateTellerDenyList {
+    teller: Address,
+    list: DenyList,
+    user: Address,
+    deny: bool,
+}
+
+impl UpdateTellerDenyList {
+    pub fn new(teller: Address, list: DenyList, user: Address, deny: bool) -> Self {
+        Self {
+            teller,
+            list,
+            user,
+            deny,
+        }
+    }
+}
+
+impl AdminAction for UpdateTellerDenyList {
+    fn target(&self) -> Address {
+        self.teller
+    }
+    fn data(&self) -> Bytes {
+        let user = (self.user,);
+        let bytes_data = match (self.list, self.deny) {
+            (DenyList::From, true) => {
+                TellerWithMultiAssetSupport::denyFromCall::new(user).abi_encode()
+            }
+            (DenyList::From, false) => {
+                TellerWithMultiAssetSupport::allowFromCall::new(user).abi_encode()
+            }
+            (DenyList::To, true) => TellerWithMultiAssetSupport::denyToCall::new(user).abi_encode(),
+            (DenyList::To, false) => {
+                TellerWithMultiAssetSupport::allowToCall::new(user).abi_encode()
+            }
+            (DenyList::Operator, true) => {
+                TellerWithMultiAssetSupport::denyOperatorCall::new(user).abi_encode()
+            }
+            (DenyList::Operator, false) => {
+                TellerWithMultiAssetSupport::allowOperatorCall::new(user).abi_encode()
+            }
+        };
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": if self.deny { "DenyTellerUser" } else { "AllowTellerUser" },
+            "teller": self.teller.to_string(),
+            "list": self.list.as_str(),
+            "user": self.user.to_string(),
+        })
+    }
+}
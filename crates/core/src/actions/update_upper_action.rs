@@ -0,0 +1,36 @@
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::SolCall;
+use serde_json::{Value, json};
+
+use crate::{
+    actions::admin_action::AdminAction, bindings::accountant::AccountantWithRateProviders,
+};
+
+pub struct UpdateUpper {
+    accountant: Address,
+    upper: u16,
+}
+
+impl UpdateUpper {
+    pub fn new(accountant: Address, upper: u16) -> Self {
+        Self { accountant, upper }
+    }
+}
+
+impl AdminAction for UpdateUpper {
+    fn target(&self) -> Address {
+        self.accountant
+    }
+    fn data(&self) -> Bytes {
+        let bytes_data =
+            AccountantWithRateProviders::updateUpperCall::new((self.upper,)).abi_encode();
+        Bytes::from(bytes_data)
+    }
+    fn describe(&self) -> Value {
+        json!({
+            "action": "UpdateUpper",
+            "accountant": self.accountant.to_string(),
+            "upper": self.upper.to_string(),
+        })
+    }
+}
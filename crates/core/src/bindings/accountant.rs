@@ -1,7 +1,7 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract AccountantWithRateProviders {
     struct RateProviderData {
         bool isPeggedToBase;
@@ -11,7 +11,24 @@ sol! {
         function setRateProviderData(address asset, bool isPeggedToBase, address rateProvider) external;
         function rateProviderData(address asset) external view returns(RateProviderData memory rpd);
         function base() external view returns(address base);
+        function vault() external view returns(address);
         function updatePerformanceFee(uint16 fee) external;
         function updatePlatformFee(uint16 fee) external;
+        function updateManagementFee(uint16 fee) external;
+        function pause() external;
+        function unpause() external;
+        function updateUpper(uint16 allowedExchangeRateChangeUpper) external;
+        function updateLower(uint16 allowedExchangeRateChangeLower) external;
+        function updateDelay(uint24 minimumUpdateDelayInSeconds) external;
+        function updatePayoutAddress(address payoutAddress) external;
+        function updateExchangeRate(uint96 newExchangeRate) external;
+        function accountantState() external view returns(address payoutAddress, uint96 highwaterMark, uint128 feesOwedInBase, uint128 totalSharesLastUpdate, uint96 exchangeRate, uint16 allowedExchangeRateChangeUpper, uint16 allowedExchangeRateChangeLower, uint64 lastUpdateTimestamp, bool isPaused, uint24 minimumUpdateDelayInSeconds, uint16 platformFee, uint16 performanceFee);
+    }
+
+    // Legacy accountant, no highwater mark and a uint32 update delay.
+    #[sol(rpc, abi)]
+    contract LegacyAccountantWithRateProviders {
+        function updateDelay(uint32 minimumUpdateDelayInSeconds) external;
+        function accountantState() external view returns(address payoutAddress, uint128 feesOwedInBase, uint128 totalSharesLastUpdate, uint96 exchangeRate, uint16 allowedExchangeRateChangeUpper, uint16 allowedExchangeRateChangeLower, uint64 lastUpdateTimestamp, bool isPaused, uint32 minimumUpdateDelayInSeconds, uint16 managementFee);
     }
 }
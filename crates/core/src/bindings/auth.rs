@@ -0,0 +1,12 @@
+use alloy::sol;
+
+sol! {
+    // Solmate Auth, inherited by every product contract.
+    #[sol(rpc, abi)]
+    contract Auth {
+        function owner() external view returns(address);
+        function authority() external view returns(address);
+        function setAuthority(address newAuthority) external;
+        function transferOwnership(address newOwner) external;
+    }
+}
@@ -1,7 +1,7 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract BoringOnChainQueue {
     struct WithdrawAsset {
         bool allowWithdraws;
@@ -12,6 +12,16 @@ sol! {
         uint96 minimumShares;
         uint256 withdrawCapacity;
     }
+    struct OnChainWithdraw {
+        uint96 nonce;
+        address user;
+        address assetOut;
+        uint128 amountOfShares;
+        uint128 amountOfAssets;
+        uint40 creationTime;
+        uint24 secondsToMaturity;
+        uint24 secondsToDeadline;
+    }
     function updateWithdrawAsset(
         address assetOut,
         uint24 secondsToMaturity,
@@ -21,6 +31,19 @@ sol! {
         uint96 minimumShares
     ) external;
     function stopWithdrawsInAsset(address assetOut) external;
+    function setWithdrawCapacity(address assetOut, uint256 withdrawCapacity) external;
+    function withdrawAssets(address asset) external view returns(bool allowWithdraws, uint24 secondsToMaturity, uint24 minimumSecondsToDeadline, uint16 minDiscount, uint16 maxDiscount, uint96 minimumShares, uint256 withdrawCapacity);
+    function pause() external;
+    function unpause() external;
+    function isPaused() external view returns(bool);
+    function cancelUserWithdraws(OnChainWithdraw[] calldata requests) external returns(bytes32[] memory canceledRequestIds);
+    function getRequestIds() external view returns(bytes32[] memory);
+    function boringVault() external view returns(address);
+    }
+
+    // Legacy queue, withdraw assets have no withdraw capacity.
+    #[sol(rpc, abi)]
+    contract LegacyBoringOnChainQueue {
     function withdrawAssets(address asset) external view returns(bool allowWithdraws, uint24 secondsToMaturity, uint24 minimumSecondsToDeadline, uint16 minDiscount, uint16 maxDiscount, uint96 minimumShares);
     }
 }
@@ -0,0 +1,10 @@
+use alloy::sol;
+
+sol! {
+    // Ownership and authority are managed through the Auth binding.
+    #[sol(rpc, abi)]
+    contract BoringVault {
+        function hook() external view returns(address);
+        function setBeforeTransferHook(address _hook) external;
+    }
+}
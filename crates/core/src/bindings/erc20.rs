@@ -0,0 +1,13 @@
+use alloy::sol;
+
+sol! {
+    #[sol(rpc, abi)]
+    contract ERC20 {
+        function decimals() external view returns(uint8);
+        function symbol() external view returns(string);
+        function balanceOf(address account) external view returns(uint256);
+        function allowance(address owner, address spender) external view returns(uint256);
+        function transfer(address to, uint256 amount) external returns(bool);
+        function approve(address spender, uint256 amount) external returns(bool);
+    }
+}
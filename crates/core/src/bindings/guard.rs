@@ -0,0 +1,30 @@
+use alloy::sol;
+
+sol! {
+    // Safe transaction guard, module guard functions are only implemented by Safe 1.5 guards.
+    #[sol(rpc, abi)]
+    #[allow(clippy::too_many_arguments)]
+    contract Guard {
+        function supportsInterface(bytes4 interfaceId) external view returns(bool);
+        function checkTransaction(
+            address to,
+            uint256 value,
+            bytes memory data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address payable refundReceiver,
+            bytes memory signatures,
+            address msgSender
+        ) external;
+        function checkModuleTransaction(
+            address to,
+            uint256 value,
+            bytes memory data,
+            uint8 operation,
+            address module
+        ) external returns(bytes32 moduleTxHash);
+    }
+}
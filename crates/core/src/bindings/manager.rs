@@ -1,8 +1,13 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract ManagerWithMerkleVerification {
         function setManageRoot(address strategist, bytes32 root) external;
+        function manageRoot(address strategist) external view returns(bytes32);
+        function vault() external view returns(address);
+        function pause() external;
+        function unpause() external;
+        function isPaused() external view returns(bool);
     }
 }
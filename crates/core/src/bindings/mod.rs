@@ -1,8 +1,13 @@
 pub mod accountant;
+pub mod auth;
 pub mod boring_queue;
+pub mod boring_vault;
+pub mod erc20;
+pub mod guard;
 pub mod manager;
 pub mod multisend;
 pub mod multisig;
 pub mod roles_authority;
+pub mod sign_message_lib;
 pub mod teller;
 pub mod timelock;
@@ -1,8 +1,15 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract MutliSendCallOnly {
         function multiSend(bytes memory transactions) external;
     }
 }
+
+sol! {
+    #[sol(rpc, abi)]
+    contract MultiSend {
+        function multiSend(bytes memory transactions) external;
+    }
+}
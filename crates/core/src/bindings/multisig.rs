@@ -1,7 +1,8 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
+    #[allow(clippy::too_many_arguments)]
     contract GnosisSafe {
         event ApproveHash(bytes32 indexed approvedHash, address indexed owner);
         function execTransactionFromModule(address to, uint256 value, bytes memory data, uint8 operation);
@@ -18,6 +19,9 @@ sol! {
             uint256 _nonce
         ) public view returns (bytes32);
         function enableModule(address module) external;
+        function disableModule(address prevModule, address module) external;
+        function getModulesPaginated(address start, uint256 pageSize) external view returns(address[] memory array, address next);
+        function setGuard(address guard) external;
         function approveHash(bytes32 safeHash) external;
         function getOwners() external view returns(address[] memory owners);
         function getThreshold() external view returns(uint256 threshold);
@@ -35,5 +39,9 @@ sol! {
             bytes memory signatures
         ) external;
         function approvedHashes(address owner, bytes32 safeHash) external view returns(uint256);
+        function addOwnerWithThreshold(address owner, uint256 _threshold) external;
+        function removeOwner(address prevOwner, address owner, uint256 _threshold) external;
+        function swapOwner(address prevOwner, address oldOwner, address newOwner) external;
+        function changeThreshold(uint256 _threshold) external;
     }
 }
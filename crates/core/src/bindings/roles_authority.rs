@@ -1,9 +1,15 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract RolesAuthority {
+        event UserRoleUpdated(address indexed user, uint8 indexed role, bool enabled);
+        event PublicCapabilityUpdated(address indexed target, bytes4 indexed functionSig, bool enabled);
+        event RoleCapabilityUpdated(uint8 indexed role, address indexed target, bytes4 indexed functionSig, bool enabled);
+        event OwnershipTransferred(address indexed user, address indexed newOwner);
+        event AuthorityUpdated(address indexed user, address indexed newAuthority);
         function doesUserHaveRole(address user, uint8 role) public view virtual returns (bool);
+        function getUserRoles(address user) external view returns (bytes32);
         function doesRoleHaveCapability(
             uint8 role,
             address target,
@@ -0,0 +1,8 @@
+use alloy::sol;
+
+sol! {
+    #[sol(rpc, abi)]
+    contract SignMessageLib {
+        function signMessage(bytes calldata _data) external;
+    }
+}
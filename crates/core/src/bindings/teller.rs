@@ -1,7 +1,7 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract TellerWithMultiAssetSupport {
         struct Asset {
             bool allowDeposits;
@@ -14,5 +14,21 @@ sol! {
         function isSupported(address asset) external view returns(bool supported);
         function addAsset(address asset) external;
         function removeAsset(address asset) external;
+        function pause() external;
+        function unpause() external;
+        function isPaused() external view returns(bool);
+        function vault() external view returns(address);
+        function accountant() external view returns(address);
+        function setShareLockPeriod(uint64 _shareLockPeriod) external;
+        function shareLockPeriod() external view returns(uint64);
+        function setDepositCap(uint112 cap) external;
+        function depositCap() external view returns(uint112);
+        function denyFrom(address user) external;
+        function allowFrom(address user) external;
+        function denyTo(address user) external;
+        function allowTo(address user) external;
+        function denyOperator(address user) external;
+        function allowOperator(address user) external;
+        function beforeTransferData(address user) external view returns(bool denyFrom, bool denyTo, bool denyOperator, bool permissionedOperator, uint256 shareUnlockTime);
     }
 }
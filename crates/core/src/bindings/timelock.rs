@@ -1,10 +1,12 @@
 use alloy::sol;
 
 sol! {
-    #[sol(rpc)]
+    #[sol(rpc, abi)]
     contract Timelock {
         function scheduleBatch(address[] memory targets, uint256[] memory values, bytes[] memory payloads, bytes32 predecessor, bytes32 salt, uint256 delay) external;
         function executeBatch(address[] memory targets, uint256[] memory values, bytes[] memory payloads, bytes32 predecessor, bytes32 salt) external;
         function getMinDelay() external view returns(uint256 delay);
+        function PROPOSER_ROLE() external view returns(bytes32);
+        event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
     }
 }
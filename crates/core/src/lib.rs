@@ -1,91 +1,456 @@
 pub mod actions;
 pub mod bindings;
 pub mod processors;
+pub mod report;
 pub mod types;
 pub mod utils;
 use crate::{
-    actions::{multisend_utils::create_multisend_data, timelock_action::TimelockAction},
+    actions::{
+        multisend_utils::{build_multisig_tx_config, split_txs_by_limits},
+        timelock_action::TimelockAction,
+    },
     bindings::{
-        manager::ManagerWithMerkleVerification, multisend::MutliSendCallOnly, multisig::GnosisSafe,
-        timelock::Timelock,
+        auth::Auth, manager::ManagerWithMerkleVerification, multisend::MutliSendCallOnly,
+        multisig::GnosisSafe, roles_authority::RolesAuthority, timelock::Timelock,
     },
     processors::{
-        asset_update::process_asset_updates, roles_update::process_roles_updates,
-        root_update::process_merkle_root_update, solver_update::process_solver_update,
-        strategist_roles::process_strategist_roles_update, update_fees::process_fee_updates,
+        accountant_update::process_accountant_updates,
+        asset_update::process_asset_updates,
+        emergency_pause::process_emergency_pause,
+        exchange_rate_update::process_exchange_rate_update,
+        owner_rotation::{process_owner_rotation, rotation_contracts},
+        queue_update::process_queue_updates,
+        raw_calls::process_raw_calls,
+        roles_update::process_roles_updates,
+        root_update::process_merkle_root_update,
+        safe_modules::{check_guard_allows_txs, process_safe_modules_update},
+        safe_owners::process_safe_owners_update,
+        sign_message::process_sign_message,
+        solver_update::process_solver_update,
+        strategist_roles::process_strategist_roles_update,
+        teller_update::process_teller_updates,
+        treasury::{process_approvals, process_transfers},
+        update_fees::process_fee_updates,
+        vault_update::process_vault_updates,
+    },
+    types::{
+        selector_registry::SelectorRegistry,
+        transaction::{Operation, Transaction},
+    },
+    utils::simulate::{
+        estimate_gas_for_txs, generate_safe_hash_and_return_params, simulate_calls_from_new_safe,
     },
-    types::transaction::Transaction,
-    utils::simulate::generate_safe_hash_and_return_params,
 };
 use actions::admin_action::AdminAction;
 use alloy::network::EthereumWallet;
-use alloy::primitives::{Address, Bytes, FixedBytes, U256};
+use alloy::primitives::{Address, B256, Bytes, FixedBytes, U256, keccak256};
 use alloy::providers::Provider;
 use alloy::signers::ledger::{self, LedgerSigner};
 use alloy::signers::trezor::{self, TrezorSigner};
-use alloy::{providers::ProviderBuilder, sol_types::SolCall};
+use alloy::{
+    providers::ProviderBuilder,
+    sol_types::{SolCall, SolValue},
+};
 use dotenv::dotenv;
 use eyre::{Result, eyre};
-use hex;
 use processors::withdraw_asset_update::process_queue_asset_updates;
 use reqwest::Client;
 use serde_json::{Value, json};
 use std::collections::{HashMap, HashSet};
 use std::env;
-use types::{config_wrapper::ConfigWrapper, simulation_config::SimulationConfig};
+use types::{
+    admin_batch::AdminBatch,
+    config_wrapper::ConfigWrapper,
+    permission_matrix::{PermissionFormat, PermissionLabels, PermissionMatrix},
+    simulation_config::SimulationConfig,
+};
 pub use utils::simulate::{
-    simulate_admin_tx_and_generate_safe_hash, simulate_timelock_admin_txs_and_generate_safe_hashes,
+    simulate_admin_tx_and_generate_safe_hash, simulate_admin_txs_in_sequence,
+    simulate_timelock_admin_txs_and_generate_safe_hashes,
 };
-// Should return the min number of actions which really should just be 2
-// could revert if you have a value that relies on multiple multisigs...
-pub async fn generate_admin_actions_from_json(
-    value: Value,
-) -> Result<(Vec<SimulationConfig>, Vec<Vec<String>>)> {
-    dotenv().ok();
-    // Extract the required fields from the JSON
+
+// A single network worth of actions extracted from an action file.
+struct NetworkSection {
+    network_id: u32,
+    nonce: Option<NonceSpec>,
+    actions: Vec<Value>,
+}
+
+// Nonce given in an action file, either one nonce for the only multisig involved, or a map
+// from multisig address to nonce when the actions span several multisigs.
+enum NonceSpec {
+    Single(u32),
+    PerMultisig(HashMap<Address, u32>),
+}
+
+// Action files either hold a single `network_id`, or a `networks` list whose entries are
+// network ids sharing the top level `actions`, or full sections with their own actions.
+fn extract_network_sections(value: &Value) -> Result<Vec<NetworkSection>> {
+    let networks = match value.get("networks") {
+        Some(networks) => networks
+            .as_array()
+            .ok_or_else(|| eyre!("networks must be an array"))?,
+        None => return Ok(vec![parse_network_section(value, None)?]),
+    };
+
+    if value.get("network_id").is_some() || value.get("nonce").is_some() {
+        return Err(eyre!(
+            "network_id and nonce must be set inside each networks entry, not at the top level"
+        ));
+    }
+
+    let shared_actions = value.get("actions");
+    let mut sections = Vec::with_capacity(networks.len());
+    let mut seen = HashSet::new();
+    for network in networks {
+        let section = if network.is_u64() {
+            parse_network_section(&json!({ "network_id": network }), shared_actions)?
+        } else {
+            parse_network_section(network, shared_actions)?
+        };
+        if !seen.insert(section.network_id) {
+            return Err(eyre!(
+                "network_id {} appears more than once in networks",
+                section.network_id
+            ));
+        }
+        sections.push(section);
+    }
+
+    if sections.is_empty() {
+        return Err(eyre!("networks must not be empty"));
+    }
+
+    Ok(sections)
+}
+
+fn parse_network_section(value: &Value, shared_actions: Option<&Value>) -> Result<NetworkSection> {
     let network_id = value["network_id"]
         .as_u64()
         .ok_or_else(|| eyre!("network_id must be a number"))? as u32;
 
-    let nonce = value["nonce"]
-        .as_u64()
-        .ok_or_else(|| eyre!("nonce must be a number"))? as u32;
+    // Nonce is optional, if not provided it is read from the multisig.
+    let nonce = match value.get("nonce") {
+        Some(Value::Object(nonces)) => {
+            let mut per_multisig = HashMap::new();
+            for (multisig, nonce) in nonces {
+                let nonce = nonce
+                    .as_u64()
+                    .ok_or_else(|| eyre!("nonce for multisig {} must be a number", multisig))?;
+                per_multisig.insert(multisig.parse::<Address>()?, nonce as u32);
+            }
+            Some(NonceSpec::PerMultisig(per_multisig))
+        }
+        Some(nonce) => Some(NonceSpec::Single(
+            nonce
+                .as_u64()
+                .ok_or_else(|| eyre!("nonce must be a number or a map of multisig to nonce"))?
+                as u32,
+        )),
+        None => None,
+    };
+
+    let actions = value
+        .get("actions")
+        .or(shared_actions)
+        .and_then(|a| a.as_array())
+        .ok_or_else(|| eyre!("actions must be an array for network {}", network_id))?
+        .clone();
+
+    Ok(NetworkSection {
+        network_id,
+        nonce,
+        actions,
+    })
+}
 
-    let actions = value["actions"]
-        .as_array()
-        .ok_or_else(|| eyre!("actions must be an array"))?;
+// Reads the current nonce of a multisig.
+pub async fn get_multisig_nonce(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    multisig: Address,
+) -> Result<u32> {
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let safe = GnosisSafe::new(multisig, provider);
+    let nonce = safe.nonce().call().await?.nonce;
+    Ok(nonce.to::<u32>())
+}
+
+// Generates one batch of admin txs for every network in the action file.
+pub async fn generate_admin_batches_from_json(value: Value) -> Result<Vec<AdminBatch>> {
+    dotenv().ok();
 
     // Load config from default path
     let cw = ConfigWrapper::from_file(None)?;
 
-    // Check that all products use the same multisig address
-    let mut multisig_addresses = HashSet::new();
+    let sections = extract_network_sections(&value)?;
+    let mut batches = Vec::with_capacity(sections.len());
+    for section in sections {
+        batches.extend(generate_network_batches(&cw, &section).await?);
+    }
 
-    for action in actions {
+    Ok(batches)
+}
+
+// Partitions the actions of a network by the multisig owning each product, and generates one
+// batch per multisig.
+async fn generate_network_batches(
+    cw: &ConfigWrapper,
+    section: &NetworkSection,
+) -> Result<Vec<AdminBatch>> {
+    let network_id = section.network_id;
+
+    // Group actions by multisig, keeping the order multisigs first appear in.
+    let mut groups: Vec<(Address, Vec<&Value>)> = Vec::new();
+    for action in &section.actions {
         let product = action["product"]
             .as_str()
             .ok_or_else(|| eyre!("product must be a string"))?;
 
         // Get multisig address for this product
-        let multisig_address =
-            cw.get_product_config_value(product, network_id, "multisig_address")?;
+        let multisig_address: Address = cw
+            .get_product_config_value(product, network_id, "multisig_address")?
+            .parse()?;
+
+        match groups.iter_mut().find(|(m, _)| *m == multisig_address) {
+            Some((_, actions)) => actions.push(action),
+            None => groups.push((multisig_address, vec![action])),
+        }
+    }
 
-        multisig_addresses.insert(multisig_address);
+    if groups.is_empty() {
+        return Err(eyre!("No actions provided for network {}", network_id));
     }
 
-    if multisig_addresses.len() > 1 {
+    if matches!(section.nonce, Some(NonceSpec::Single(_))) && groups.len() > 1 {
         return Err(eyre!(
-            "Cannot combine actions for products with different multisig addresses"
+            "Actions on network {} span {} multisigs, nonce must be omitted or given per multisig",
+            network_id,
+            groups.len()
         ));
     }
 
-    // Now process each action
-    let mut admin_actions: HashMap<Option<Address>, Vec<Box<dyn AdminAction>>> = HashMap::new();
+    let mut batches = Vec::with_capacity(groups.len());
+    for (multisig_address, actions) in groups {
+        let nonce = match &section.nonce {
+            Some(NonceSpec::Single(nonce)) => Some(*nonce),
+            Some(NonceSpec::PerMultisig(nonces)) => nonces.get(&multisig_address).copied(),
+            None => None,
+        };
+        batches.push(
+            generate_multisig_batch(cw, network_id, multisig_address, nonce, &actions).await?,
+        );
+    }
+
+    Ok(batches)
+}
+
+// Actions routed through the same timelock, or sent directly by the multisig if `None`.
+type TimelockGroup = (Option<Address>, Vec<Box<dyn AdminAction>>);
+
+// A tx of the batch, either sent directly by the multisig, or a chunk of actions scheduled on a
+// timelock and executed after its delay.
+enum PlannedTx {
+    Direct(Box<dyn AdminAction>),
+    Timelock(TimelockAction),
+}
+
+// Txs built from the planned txs, with the txs and descriptions to send before and after the
+// timelock delay, and the planned index, proposal index and execution index of every timelock
+// chunk.
+type PlannedTxs = (
+    Vec<Transaction>,
+    Vec<Transaction>,
+    [Vec<String>; 2],
+    Vec<(usize, usize, usize)>,
+);
+
+fn build_planned_txs(
+    planned: &mut [PlannedTx],
+    selectors: &SelectorRegistry,
+    nonce: u32,
+) -> Result<PlannedTxs> {
+    // Chunks of the same timelock are chained, each one only executable after the previous one,
+    // and salted with the Safe nonce and their index in the batch so identical chunks get distinct
+    // ids.
+    let mut previous: Vec<(Address, B256)> = Vec::new();
+    for (index, planned_tx) in planned.iter_mut().enumerate() {
+        if let PlannedTx::Timelock(timelock_action) = planned_tx {
+            let timelock = timelock_action.target();
+            let predecessor = previous
+                .iter()
+                .find(|(t, _)| *t == timelock)
+                .map_or(B256::ZERO, |(_, id)| *id);
+            let salt = keccak256((U256::from(nonce), U256::from(index)).abi_encode_params());
+            timelock_action.chain(predecessor, salt);
+            let id = timelock_action.operation_id();
+            match previous.iter_mut().find(|(t, _)| *t == timelock) {
+                Some(entry) => entry.1 = id,
+                None => previous.push((timelock, id)),
+            }
+        }
+    }
+
+    let to_tx = |action: &dyn AdminAction| Transaction {
+        to: action.target(),
+        value: action.value(),
+        data: action.data(),
+        operation: action.operation(),
+    };
+
+    let mut txs_0 = Vec::new();
+    let mut txs_1 = Vec::new();
+    let mut descriptions: [Vec<String>; 2] = [vec![], vec![]];
+    let mut timelock_txs = Vec::new();
+    for (index, planned_tx) in planned.iter_mut().enumerate() {
+        match planned_tx {
+            PlannedTx::Direct(action) => {
+                txs_0.push(to_tx(action.as_ref()));
+                descriptions[0].push(serde_json::to_string_pretty(
+                    &action.describe_call(selectors),
+                )?);
+            }
+            PlannedTx::Timelock(timelock_action) => {
+                timelock_txs.push((index, txs_0.len(), txs_1.len()));
+                txs_0.push(to_tx(timelock_action));
+                descriptions[0].push(serde_json::to_string_pretty(
+                    &timelock_action.describe_call(selectors),
+                )?);
+                timelock_action.toggle_mode(); // Change mode to execute.
+                txs_1.push(to_tx(timelock_action));
+                descriptions[1].push(serde_json::to_string_pretty(
+                    &timelock_action.describe_call(selectors),
+                )?);
+                timelock_action.toggle_mode();
+            }
+        }
+    }
+    Ok((txs_0, txs_1, descriptions, timelock_txs))
+}
+
+// Checks the txs executed after every `setGuard` call of the batch pass the guard it sets, before
+// any of them is simulated. A guard applies until the next `setGuard`, the last one also covering
+// the timelock executions. Safe tx boundaries are only known once the batch is split, so every tx
+// is checked on its own, and grouped with the other txs after it through MultiSend.
+async fn check_new_guards(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    multisig: Address,
+    nonce: u32,
+    txs_0: &[Transaction],
+    txs_1: &[Transaction],
+) -> Result<()> {
+    let mut guards = Vec::new();
+    for (index, tx) in txs_0.iter().enumerate() {
+        if tx.to == multisig && tx.data.starts_with(&GnosisSafe::setGuardCall::SELECTOR) {
+            guards.push((
+                index,
+                GnosisSafe::setGuardCall::abi_decode(&tx.data, true)?.guard,
+            ));
+        }
+    }
+
+    for (i, (index, guard)) in guards.iter().enumerate() {
+        let mut groups = match guards.get(i + 1) {
+            Some((next, _)) => vec![&txs_0[index + 1..*next]],
+            None => vec![&txs_0[index + 1..], txs_1],
+        };
+        groups.retain(|group| !group.is_empty());
+
+        let mut configs = Vec::new();
+        for group in groups {
+            for tx in group {
+                configs.push(build_multisig_tx_config(
+                    cw,
+                    network_id,
+                    multisig,
+                    nonce,
+                    vec![tx.clone()],
+                )?);
+            }
+            if group.len() > 1 {
+                configs.push(build_multisig_tx_config(
+                    cw,
+                    network_id,
+                    multisig,
+                    nonce,
+                    group.to_vec(),
+                )?);
+            }
+        }
+        check_guard_allows_txs(cw, network_id, multisig, *guard, &configs).await?;
+    }
+
+    Ok(())
+}
+
+// Checks every contract the multisig delegatecalls into has code, the MultiSend contracts batching
+// the Safe txs included. Delegatecalls to an address without code succeed without doing anything,
+// so a MultiSend or SignMessageLib that is not deployed on a network would silently drop the txs.
+async fn check_delegate_call_targets(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    configs: &[SimulationConfig],
+    txs_0: &[Transaction],
+    txs_1: &[Transaction],
+) -> Result<()> {
+    let mut targets = Vec::new();
+    let config_targets = configs
+        .iter()
+        .filter(|config| config.operation == Operation::DelegateCall.as_u8())
+        .map(|config| config.to());
+    let tx_targets = txs_0
+        .iter()
+        .chain(txs_1)
+        .filter(|tx| tx.operation == Operation::DelegateCall)
+        .map(|tx| tx.to);
+    for target in config_targets.chain(tx_targets) {
+        if !targets.contains(&target) {
+            targets.push(target);
+        }
+    }
+    if targets.is_empty() {
+        return Ok(());
+    }
+
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    for target in targets {
+        if provider.get_code_at(target).await?.is_empty() {
+            return Err(eyre!(
+                "Delegatecall target {} has no code on network {}, check config.toml",
+                target,
+                network_id
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Should return the min number of actions which really should just be 2
+async fn generate_multisig_batch(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    multisig_address: Address,
+    nonce: Option<u32>,
+    actions: &[&Value],
+) -> Result<AdminBatch> {
+    let mut products: Vec<String> = Vec::new();
+
+    // Now process each action, grouped by timelock in the order they first appear.
+    let mut admin_actions: Vec<TimelockGroup> = Vec::new();
 
     for action in actions {
         let product = action["product"]
             .as_str()
             .ok_or_else(|| eyre!("product must be a string"))?;
+        if !products.iter().any(|p| p == product) {
+            products.push(product.to_string());
+        }
 
         let timelock_addr =
             match cw.get_product_config_value(product, network_id, "timelock_address") {
@@ -93,17 +458,29 @@ pub async fn generate_admin_actions_from_json(
                 Err(_) => None, // Timelock is optional
             };
 
-        let mut action_sub_set = admin_actions.entry(timelock_addr).or_insert_with(Vec::new);
+        let index = match admin_actions.iter().position(|(t, _)| *t == timelock_addr) {
+            Some(index) => index,
+            None => {
+                admin_actions.push((timelock_addr, Vec::new()));
+                admin_actions.len() - 1
+            }
+        };
+        let action_sub_set = &mut admin_actions[index].1;
+
+        // Process emergency pauses before any other update of the product
+        if let Some(pause_data) = action.get("emergency_pause") {
+            process_emergency_pause(action_sub_set, cw, product, network_id, pause_data).await?;
+        }
 
         // Process merkle root updates if present
         if let Some(root_str) = action["new_root"].as_str() {
-            process_merkle_root_update(&mut action_sub_set, &cw, product, network_id, root_str)?;
+            process_merkle_root_update(action_sub_set, cw, product, network_id, root_str)?;
         }
 
         // Process asset updates if present
         if let Some(new_assets) = action["new_assets"].as_array() {
             for asset_update in new_assets {
-                process_asset_updates(&mut action_sub_set, &cw, product, network_id, asset_update)
+                process_asset_updates(action_sub_set, cw, product, network_id, asset_update)
                     .await?;
             }
         }
@@ -111,26 +488,57 @@ pub async fn generate_admin_actions_from_json(
         // Process withdraw asset updates if present.
         if let Some(new_queue_assets) = action["new_queue_assets"].as_array() {
             for queue_asset in new_queue_assets {
-                process_queue_asset_updates(action_sub_set, &cw, product, network_id, queue_asset)
+                process_queue_asset_updates(action_sub_set, cw, product, network_id, queue_asset)
                     .await?;
             }
         }
 
+        // Process queue level updates if present
+        if let Some(queue_data) = action.get("update_queue") {
+            process_queue_updates(action_sub_set, cw, product, network_id, queue_data).await?;
+        }
+
         if let Some(fee_data) = action["update_fees"].as_object() {
             process_fee_updates(
-                &mut action_sub_set,
-                &cw,
+                action_sub_set,
+                cw,
                 product,
                 network_id,
                 &Value::Object(fee_data.clone()),
-            )?;
+            )
+            .await?;
+        }
+
+        // Process teller updates if present
+        if let Some(teller_data) = action.get("update_teller") {
+            process_teller_updates(action_sub_set, cw, product, network_id, teller_data).await?;
+        }
+
+        // Process accountant updates if present
+        if let Some(accountant_data) = action.get("update_accountant") {
+            process_accountant_updates(action_sub_set, cw, product, network_id, accountant_data)
+                .await?;
+        }
+
+        // Process manual exchange rate updates, after any unpause from update_accountant
+        if let Some(exchange_rate_data) = action.get("update_exchange_rate") {
+            let unpaused_in_batch = action["update_accountant"]["paused"] == Value::Bool(false);
+            process_exchange_rate_update(
+                action_sub_set,
+                cw,
+                product,
+                network_id,
+                exchange_rate_data,
+                unpaused_in_batch,
+            )
+            .await?;
         }
 
         // Process solver updates if present
         if let Some(solver_data) = action["update_solver"].as_object() {
             process_solver_update(
-                &mut action_sub_set,
-                &cw,
+                action_sub_set,
+                cw,
                 product,
                 network_id,
                 &Value::Object(solver_data.clone()),
@@ -140,129 +548,319 @@ pub async fn generate_admin_actions_from_json(
         // Process strategist updates (roles and potentially Merkle root for removal)
         if let Some(strategist_update_data_val) = action.get("update_strategist") {
             process_strategist_roles_update(
-                &mut action_sub_set,
-                &cw,
+                action_sub_set,
+                cw,
                 product,
                 network_id,
                 strategist_update_data_val, // Pass the original Value
-            )?;
+            )
+            .await?;
         }
 
         // Process new roles updates
         if let Some(new_roles) = action.get("new_roles") {
-            process_roles_updates(&mut action_sub_set, &cw, product, network_id, new_roles)?;
+            process_roles_updates(action_sub_set, cw, product, network_id, new_roles)?;
         }
-    }
 
-    // Get the multisig address (we know there's only one from earlier validation)
-    let multisig_address: Address = multisig_addresses.into_iter().next().unwrap().parse()?;
+        // Process message signing through SignMessageLib
+        if let Some(sign_message_data) = action.get("sign_message") {
+            process_sign_message(action_sub_set, cw, network_id, sign_message_data)?;
+        }
 
-    let mut txs_0 = Vec::new();
-    let mut txs_1 = Vec::new();
+        // Process treasury transfers and approvals, always sent directly by the multisig
+        if let Some(transfers) = action.get("transfers") {
+            process_transfers(action_sub_set, cw, product, network_id, transfers).await?;
+        }
+        if let Some(approvals) = action.get("approvals") {
+            process_approvals(action_sub_set, cw, product, network_id, approvals).await?;
+        }
 
-    let mut descriptions = Vec::new();
-    descriptions.push(vec![]);
-    descriptions.push(vec![]);
+        // Process raw calls encoded from function signatures
+        if let Some(raw_calls) = action.get("raw_calls") {
+            process_raw_calls(action_sub_set, cw, product, network_id, raw_calls)?;
+        }
+
+        // Process Safe owner changes, always sent directly by the multisig
+        if let Some(owners_data) = action.get("safe_owners") {
+            process_safe_owners_update(action_sub_set, cw, product, network_id, owners_data)
+                .await?;
+        }
+
+        // Process Safe module and guard changes, always sent directly by the multisig
+        if let Some(modules_data) = action.get("safe_modules") {
+            process_safe_modules_update(action_sub_set, cw, product, network_id, modules_data)
+                .await?;
+        }
+
+        // Process owner rotations, after every other update made by the current owner
+        if let Some(rotation_data) = action.get("rotate_owner") {
+            process_owner_rotation(action_sub_set, cw, product, network_id, rotation_data).await?;
+        }
 
+        // Process vault updates last, since they may transfer ownership away from the multisig
+        if let Some(vault_data) = action.get("update_vault") {
+            process_vault_updates(action_sub_set, cw, product, network_id, vault_data).await?;
+        }
+    }
+
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => get_multisig_nonce(cw, network_id, multisig_address).await?,
+    };
+
+    let limits = cw.get_batch_limits(network_id)?;
+    let mut planned: Vec<PlannedTx> = Vec::new();
     for (timelock_addr, actions) in admin_actions {
         match timelock_addr {
             Some(addr) => {
+                // Delegatecalls run in the context of the multisig itself, and calls to the
+                // multisig or spending its funds must come from the multisig, so they can not be
+                // routed through the timelock and are sent directly instead.
+                let (direct_calls, actions): (Vec<_>, Vec<_>) =
+                    actions.into_iter().partition(|action| {
+                        action.operation() == Operation::DelegateCall
+                            || action.target() == multisig_address
+                            || action.requires_multisig_sender()
+                    });
+                planned.extend(direct_calls.into_iter().map(PlannedTx::Direct));
+                if actions.is_empty() {
+                    continue;
+                }
+
                 // Create propose and execute timelock actions
                 // Read the min delay.
                 let rpc_url = cw.get_rpc_url(network_id)?;
                 let provider = ProviderBuilder::new().on_builtin(&rpc_url).await?;
                 let timelock = Timelock::new(addr, provider);
                 let min_delay = timelock.getMinDelay().call().await?.delay;
-                let mut timelock_action = TimelockAction::new(addr, min_delay, actions);
-                txs_0.push(Transaction {
-                    to: timelock_action.target(),
-                    value: timelock_action.value(),
-                    data: timelock_action.data(),
-                });
-                descriptions[0].push(serde_json::to_string_pretty(&timelock_action.describe())?);
-                timelock_action.toggle_mode(); // Change mode to execute.
-                txs_1.push(Transaction {
-                    to: timelock_action.target(),
-                    value: timelock_action.value(),
-                    data: timelock_action.data(),
-                });
-                descriptions[1].push(serde_json::to_string_pretty(&timelock_action.describe())?);
-            }
-            None => {
+
+                // Chunk the actions into several scheduled batches if a single one would exceed
+                // the calldata limit, the proposal being the larger of the two calls.
+                let mut chunks = vec![TimelockAction::new(addr, min_delay, Vec::new())];
                 for action in actions {
-                    txs_0.push(Transaction {
-                        to: action.target(),
-                        value: action.value(),
-                        data: action.data(),
-                    });
-                    descriptions[0].push(serde_json::to_string_pretty(&action.describe())?);
+                    let chunk = chunks.last_mut().expect("chunks is never empty");
+                    chunk.push(action);
+                    if chunk.action_count() > 1
+                        && limits
+                            .max_calldata_bytes
+                            .is_some_and(|max| chunk.data().len() > max)
+                    {
+                        let action = chunk.pop().expect("chunk holds several actions");
+                        chunks.push(TimelockAction::new(addr, min_delay, vec![action]));
+                    }
                 }
+                planned.extend(chunks.into_iter().map(PlannedTx::Timelock));
             }
+            None => planned.extend(actions.into_iter().map(PlannedTx::Direct)),
         }
     }
 
-    // Convert txs to multisend txs if needed.
-    let multisend = cw.get_multisend_address(network_id as u32)?;
-    let multisend_addr: Address = multisend.parse().unwrap();
+    // Estimate gas for every tx in order if a gas limit is configured for the network, halving
+    // the scheduled batches whose proposal or execution exceeds the limit on its own.
+    let selectors = cw.get_selector_registry()?;
+    let (txs_0, txs_1, descriptions, gas_used) = loop {
+        let (txs_0, txs_1, descriptions, timelock_txs) =
+            build_planned_txs(&mut planned, &selectors, nonce)?;
+        if txs_0.is_empty() {
+            return Err(eyre!(
+                "No transactions to send for multisig {} on network {}",
+                multisig_address,
+                network_id
+            ));
+        }
+        check_new_guards(cw, network_id, multisig_address, nonce, &txs_0, &txs_1).await?;
+        let Some(max_gas) = limits.max_gas else {
+            break (txs_0, txs_1, descriptions, None);
+        };
+
+        let all_txs = txs_0
+            .iter()
+            .chain(txs_1.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        let execute_from = (!txs_1.is_empty()).then_some(txs_0.len());
+        let gas_used =
+            estimate_gas_for_txs(network_id, multisig_address, &all_txs, execute_from).await?;
+
+        let oversized = timelock_txs.iter().find_map(|&(index, propose, execute)| {
+            let too_large =
+                gas_used[propose] > max_gas || gas_used[txs_0.len() + execute] > max_gas;
+            match &planned[index] {
+                PlannedTx::Timelock(chunk) if too_large && chunk.action_count() > 1 => Some(index),
+                _ => None,
+            }
+        });
+        match oversized {
+            Some(index) => {
+                if let PlannedTx::Timelock(chunk) = &mut planned[index] {
+                    let half = chunk.split_off(chunk.action_count() / 2);
+                    planned.insert(index + 1, PlannedTx::Timelock(half));
+                }
+            }
+            None => break (txs_0, txs_1, descriptions, Some(gas_used)),
+        }
+    };
+    let (gas_used_0, gas_used_1) = match &gas_used {
+        Some(gas_used) => {
+            let (gas_used_0, gas_used_1) = gas_used.split_at(txs_0.len());
+            (Some(gas_used_0), Some(gas_used_1))
+        }
+        None => (None, None),
+    };
 
+    // Split txs into several Safe txs with consecutive nonces if they exceed the limits, and
+    // convert txs to multisend txs if needed.
     let mut final_configs = Vec::new();
-
-    match txs_0.len() {
-        0 => return Err(eyre!("No transactions to send")),
-        1 => final_configs.push(SimulationConfig {
+    let mut final_descriptions = Vec::new();
+    let mut next_nonce = nonce;
+    for chunk in split_txs_by_limits(&txs_0, gas_used_0, &limits)? {
+        final_configs.push(build_multisig_tx_config(
+            cw,
             network_id,
-            multisig: multisig_address.to_string(),
-            to: txs_0[0].to.to_string(),
-            value: txs_0[0].value.to_string(),
-            data: format!("0x{}", hex::encode(txs_0[0].data.clone())),
-            operation: 0,
-            nonce,
-        }),
-        _ => {
-            let data = create_multisend_data(txs_0);
-            final_configs.push(SimulationConfig {
-                network_id,
-                multisig: multisig_address.to_string(),
-                to: multisend_addr.to_string(),
-                value: "0".to_string(),
-                data: format!("0x{}", hex::encode(data)),
-                operation: 1,
-                nonce,
-            });
-        }
+            multisig_address,
+            next_nonce,
+            txs_0[chunk.clone()].to_vec(),
+        )?);
+        final_descriptions.push(descriptions[0][chunk].to_vec());
+        next_nonce += 1;
     }
 
-    match txs_1.len() {
-        0 => {} // Do nothing
-        1 => final_configs.push(SimulationConfig {
+    let execute_from = (!txs_1.is_empty()).then_some(final_configs.len());
+    for chunk in split_txs_by_limits(&txs_1, gas_used_1, &limits)? {
+        final_configs.push(build_multisig_tx_config(
+            cw,
             network_id,
-            multisig: multisig_address.to_string(),
-            to: txs_1[0].to.to_string(),
-            value: txs_1[0].value.to_string(),
-            data: format!("0x{}", hex::encode(txs_1[0].data.clone())),
-            operation: 0,
-            nonce: nonce + 1,
-        }),
-        _ => {
-            let data = create_multisend_data(txs_1);
-            final_configs.push(SimulationConfig {
-                network_id,
-                multisig: multisig_address.to_string(),
-                to: multisend_addr.to_string(),
-                value: "0".to_string(),
-                data: format!("0x{}", hex::encode(data)),
-                operation: 1,
-                nonce: nonce + 1,
-            });
-        }
+            multisig_address,
+            next_nonce,
+            txs_1[chunk.clone()].to_vec(),
+        )?);
+        final_descriptions.push(descriptions[1][chunk].to_vec());
+        next_nonce += 1;
     }
 
-    Ok((final_configs, descriptions))
+    check_delegate_call_targets(cw, network_id, &final_configs, &txs_0, &txs_1).await?;
+
+    Ok(AdminBatch {
+        network_id,
+        multisig: multisig_address,
+        products,
+        configs: final_configs,
+        descriptions: final_descriptions,
+        execute_from,
+    })
+}
+
+/// Rebuilds the RolesAuthority permissions of a product from its events since `from_block`, and
+/// exports them as json, csv, markdown or dot, labeling product addresses, role names and the
+/// functions known to the selector registry.
+pub async fn export_permission_matrix(
+    product: &str,
+    network_id: u32,
+    format: &str,
+    from_block: u64,
+) -> Result<String> {
+    let cw = ConfigWrapper::from_file(None)?;
+    let format = format.parse::<PermissionFormat>()?;
+    let roles_authority = cw
+        .get_product_config_value(product, network_id, "roles_authority_address")?
+        .parse::<Address>()?;
+
+    let mut labels = PermissionLabels {
+        roles: cw.get_role_registry(product, network_id)?,
+        selectors: cw.get_selector_registry()?,
+        ..Default::default()
+    };
+    for (address, label) in cw.get_product_address_labels(product, network_id) {
+        labels.addresses.entry(address).or_insert(label);
+    }
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let matrix = PermissionMatrix::fetch(provider, roles_authority, from_block).await?;
+    matrix.export(format, &labels)
+}
+
+/// Whether `user` holds any role in `roles_authority` on a network.
+pub async fn has_any_role(network_id: u32, roles_authority: &str, user: &str) -> Result<bool> {
+    let cw = ConfigWrapper::from_file(None)?;
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let roles = RolesAuthority::new(roles_authority.parse::<Address>()?, provider)
+        .getUserRoles(user.parse::<Address>()?)
+        .call()
+        .await?
+        ._0;
+    Ok(!roles.is_zero())
+}
+
+/// Decodes hex calldata into the signature and arguments of the called function, using the
+/// selector registry of the config. Unknown selectors are returned with an `UNKNOWN` signature.
+pub fn decode_calldata(data: &str) -> Result<serde_json::Value> {
+    let cw = ConfigWrapper::from_file(None)?;
+    let data = hex::decode(data.trim_start_matches("0x"))?;
+    cw.get_selector_registry()?.decode_calldata(&data)
+}
+
+/// Simulates the txs of an owner rotation batch, then has `new_owner` re-transfer ownership of every
+/// rotated contract to itself, proving it can still call owner only functions. Returns the config
+/// key, address and result of every check.
+pub async fn simulate_owner_rotation_post_check(
+    batch: &AdminBatch,
+    product: &str,
+    new_owner: &str,
+) -> Result<Vec<(String, Address, bool)>> {
+    let cw = ConfigWrapper::from_file(None)?;
+    let new_owner = new_owner.parse::<Address>()?;
+    let contracts = rotation_contracts(&cw, product, batch.network_id, new_owner)
+        .await?
+        .into_iter()
+        .filter(|contract| contract.movable)
+        .collect::<Vec<_>>();
+
+    let txs = batch
+        .configs
+        .iter()
+        .map(|config| Transaction {
+            to: config.to(),
+            value: config.value(),
+            data: config.data(),
+            operation: match config.operation {
+                1 => Operation::DelegateCall,
+                _ => Operation::Call,
+            },
+        })
+        .collect::<Vec<_>>();
+    let calls = contracts
+        .iter()
+        .map(|contract| Transaction {
+            to: contract.address,
+            value: U256::ZERO,
+            data: Bytes::from(Auth::transferOwnershipCall::new((new_owner,)).abi_encode()),
+            operation: Operation::Call,
+        })
+        .collect::<Vec<_>>();
+
+    let results = simulate_calls_from_new_safe(
+        batch.network_id,
+        batch.multisig,
+        &txs,
+        batch.execute_from,
+        new_owner,
+        &calls,
+    )
+    .await?;
+
+    Ok(contracts
+        .into_iter()
+        .zip(results)
+        .map(|(contract, success)| (contract.key, contract.address, success))
+        .collect())
 }
 
 // TODO append calldata to the end of approve hash call that has the nonce?
 pub async fn generate_root_update_txs(
-    root_str: &String,
+    root_str: &str,
     product_name: &str,
     network_id: u32,
     nonce: u32,
@@ -396,7 +994,7 @@ pub async fn generate_root_update_txs(
                 encoded_transactions.push(0u8);
 
                 // to address - 20 bytes
-                encoded_transactions.extend_from_slice(&targets[i].as_slice());
+                encoded_transactions.extend_from_slice(targets[i].as_slice());
 
                 // value - 32 bytes
                 encoded_transactions.extend_from_slice(&values[i].to_be_bytes::<32>());
@@ -682,7 +1280,7 @@ pub async fn exec_transaction(
     let input_hex = hex::encode(input);
 
     let response = client
-        .post(&format!(
+        .post(format!(
             "https://api.tenderly.co/api/v1/account/{}/project/{}/simulate",
             account_slug, project_slug
         ))
@@ -742,39 +1340,3 @@ pub async fn exec_transaction(
     let tx_hash_hex = hex::encode(tx_hash.as_slice());
     Ok(format!("{}/tx/0x{}", block_explorer_url, tx_hash_hex))
 }
-
-pub fn generate_notion_markdown(
-    title: &str,
-    safe_hash: &str,
-    tx_data: &serde_json::Value,
-    root_info: Option<(&str, &[&str])>, // (root_hash, strategist_addresses)
-) -> String {
-    format!(
-        r#"- {title}
-
-SafeTxHash: `{safe_hash}`
-
-Proposal TXN: Link
-<details>
-<summary>Transaction Details</summary>
-         ```json
-        {}
-         ```
-</details>
-
-        {}
-
-        Diff: here
-
-"#,
-        serde_json::to_string_pretty(tx_data).unwrap(),
-        if let Some((root, addresses)) = root_info {
-            format!(
-                "- Set Root: `{}` for `{}` and `{}`",
-                root, addresses[0], addresses[1]
-            )
-        } else {
-            String::new()
-        }
-    )
-}
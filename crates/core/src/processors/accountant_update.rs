@@ -0,0 +1,146 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, pause_accountant_action::PauseAccountant,
+        unpause_accountant_action::UnpauseAccountant, update_delay_action::UpdateDelay,
+        update_lower_action::UpdateLower, update_payout_address_action::UpdatePayoutAddress,
+        update_upper_action::UpdateUpper,
+    },
+    types::{accountant_state::AccountantState, config_wrapper::ConfigWrapper},
+    utils::probe::resolve_accountant_version,
+};
+use alloy::primitives::Address;
+use alloy::providers::ProviderBuilder;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Exchange rate change bounds are in bps relative to 1e4.
+const BOUNDS_ONE: u16 = 10_000;
+// Max update delay accepted by the accountant, 14 days, which also fits the uint24 of V2.
+const MAX_DELAY: u64 = 14 * 24 * 60 * 60;
+
+pub async fn process_accountant_updates(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    accountant_data: &Value,
+) -> Result<()> {
+    let paused = match accountant_data.get("paused") {
+        Some(v) => Some(
+            v.as_bool()
+                .ok_or_else(|| eyre!("paused must be a boolean"))?,
+        ),
+        None => None,
+    };
+    let upper = match accountant_data.get("upper") {
+        Some(v) => Some(v.as_u64().ok_or_else(|| eyre!("upper must be a number"))?),
+        None => None,
+    };
+    let lower = match accountant_data.get("lower") {
+        Some(v) => Some(v.as_u64().ok_or_else(|| eyre!("lower must be a number"))?),
+        None => None,
+    };
+    let delay = match accountant_data.get("delay") {
+        Some(v) => Some(v.as_u64().ok_or_else(|| eyre!("delay must be a number"))?),
+        None => None,
+    };
+    let payout_address = match accountant_data.get("payout_address") {
+        Some(v) => Some(
+            v.as_str()
+                .ok_or_else(|| eyre!("payout_address must be a string"))?
+                .parse::<Address>()?,
+        ),
+        None => None,
+    };
+
+    if paused.is_none()
+        && upper.is_none()
+        && lower.is_none()
+        && delay.is_none()
+        && payout_address.is_none()
+    {
+        return Err(eyre!(
+            "update_accountant must set at least one of paused, upper, lower, delay or payout_address"
+        ));
+    }
+
+    // Validate new values the same way the accountant does.
+    let upper = upper
+        .map(|v| match u16::try_from(v) {
+            Ok(v) if v >= BOUNDS_ONE => Ok(v),
+            _ => Err(eyre!(
+                "upper must be between {} and {}",
+                BOUNDS_ONE,
+                u16::MAX
+            )),
+        })
+        .transpose()?;
+    let lower = lower
+        .map(|v| match u16::try_from(v) {
+            Ok(v) if v <= BOUNDS_ONE => Ok(v),
+            _ => Err(eyre!("lower must be at most {}", BOUNDS_ONE)),
+        })
+        .transpose()?;
+    let delay = delay
+        .map(|v| match u32::try_from(v) {
+            Ok(v) if v as u64 <= MAX_DELAY => Ok(v),
+            _ => Err(eyre!("delay must be at most {} seconds", MAX_DELAY)),
+        })
+        .transpose()?;
+    if payout_address == Some(Address::ZERO) {
+        return Err(eyre!("payout_address must not be the zero address"));
+    }
+
+    let accountant_addr_str =
+        cw.get_product_config_value(product, network_id, "accountant_address")?;
+    let accountant_addr = accountant_addr_str.parse::<Address>()?;
+
+    // Query current accountant state
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let version =
+        resolve_accountant_version(cw, product, network_id, &provider, accountant_addr).await?;
+    let state = AccountantState::fetch(provider, accountant_addr, version).await?;
+
+    if let Some(new_upper) = upper
+        && new_upper != state.allowed_exchange_rate_change_upper
+    {
+        let action = UpdateUpper::new(accountant_addr, new_upper);
+        admin_actions.push(Box::new(action));
+    }
+
+    if let Some(new_lower) = lower
+        && new_lower != state.allowed_exchange_rate_change_lower
+    {
+        let action = UpdateLower::new(accountant_addr, new_lower);
+        admin_actions.push(Box::new(action));
+    }
+
+    if let Some(new_delay) = delay
+        && new_delay != state.minimum_update_delay_in_seconds
+    {
+        let action = UpdateDelay::new(accountant_addr, new_delay, version);
+        admin_actions.push(Box::new(action));
+    }
+
+    if let Some(new_payout_address) = payout_address
+        && new_payout_address != state.payout_address
+    {
+        let action = UpdatePayoutAddress::new(accountant_addr, new_payout_address);
+        admin_actions.push(Box::new(action));
+    }
+
+    // Pause state is changed last, so that unpausing happens after the other settings are updated.
+    match paused {
+        Some(true) if !state.is_paused => {
+            admin_actions.push(Box::new(PauseAccountant::new(accountant_addr)));
+        }
+        Some(false) if state.is_paused => {
+            admin_actions.push(Box::new(UnpauseAccountant::new(accountant_addr)));
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -5,7 +5,8 @@ use crate::{
         update_asset_data_action::UpdateAssetData,
     },
     bindings::{accountant::AccountantWithRateProviders, teller::TellerWithMultiAssetSupport},
-    types::config_wrapper::ConfigWrapper,
+    types::{config_wrapper::ConfigWrapper, contract_versions::TellerVersion},
+    utils::probe::resolve_teller_version,
 };
 use alloy::primitives::Address;
 use alloy::providers::ProviderBuilder;
@@ -78,11 +79,11 @@ pub async fn process_asset_updates(
         .as_u64()
         .ok_or_else(|| eyre!("share_premium must be a number"))? as u16;
 
-    // Query current asset data
-    let teller = TellerWithMultiAssetSupport::new(teller_addr, provider);
-    let result = teller.assetData(asset_addr).call().await;
-    match result {
-        Ok(current_asset_data) => {
+    // Query current asset data using the interface the teller supports.
+    let teller = TellerWithMultiAssetSupport::new(teller_addr, provider.clone());
+    match resolve_teller_version(cw, product, network_id, &provider, teller_addr).await? {
+        TellerVersion::V2 => {
+            let current_asset_data = teller.assetData(asset_addr).call().await?;
             if current_asset_data.asset.allowDeposits != new_allow_deposits
                 || current_asset_data.asset.allowWithdraws != new_allow_withdraws
                 || current_asset_data.asset.sharePremium != new_share_premium
@@ -97,13 +98,17 @@ pub async fn process_asset_updates(
                 admin_actions.push(Box::new(action));
             }
         }
-        Err(_) => {
-            // Call failed try using legacy teller interface.
+        TellerVersion::V1 => {
             if new_allow_deposits != new_allow_withdraws {
                 return Err(eyre!(
                     "Legacy teller interface requires allow_deposits and allow_withdraws to be the same value"
                 ));
             }
+            if new_share_premium != 0 {
+                return Err(eyre!(
+                    "Legacy teller interface does not support share_premium"
+                ));
+            }
             let is_supported = teller.isSupported(asset_addr).call().await?;
             if !is_supported.supported && new_allow_deposits {
                 // Create addAsset action
@@ -0,0 +1,106 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, pause_accountant_action::PauseAccountant,
+        pause_manager_action::PauseManager, pause_queue_action::PauseQueue,
+        pause_teller_action::PauseTeller,
+    },
+    bindings::{
+        boring_queue::BoringOnChainQueue, manager::ManagerWithMerkleVerification,
+        teller::TellerWithMultiAssetSupport,
+    },
+    types::{accountant_state::AccountantState, config_wrapper::ConfigWrapper},
+    utils::probe::resolve_accountant_version,
+};
+use alloy::primitives::Address;
+use alloy::providers::ProviderBuilder;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Configured address of a product contract, `None` if missing or not set.
+fn configured_address(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    key: &str,
+) -> Option<Address> {
+    cw.get_product_config_value(product, network_id, key)
+        .ok()
+        .and_then(|addr| addr.parse::<Address>().ok())
+        .filter(|addr| *addr != Address::ZERO)
+}
+
+// Pauses every configured teller, queue, accountant and manager of a product that is not paused
+// yet. Deposits are stopped first, then withdraws, then exchange rate updates and strategist
+// management.
+pub async fn process_emergency_pause(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    pause_data: &Value,
+) -> Result<()> {
+    if !pause_data
+        .as_bool()
+        .ok_or_else(|| eyre!("emergency_pause must be a boolean"))?
+    {
+        return Ok(());
+    }
+
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let mut pause_count = 0;
+
+    if let Some(teller_addr) = configured_address(cw, product, network_id, "teller_address") {
+        let teller = TellerWithMultiAssetSupport::new(teller_addr, provider.clone());
+        if teller.isPaused().call().await?._0 {
+            println!("Teller {} is already paused", teller_addr);
+        } else {
+            admin_actions.push(Box::new(PauseTeller::new(teller_addr)));
+            pause_count += 1;
+        }
+    }
+
+    if let Some(queue_addr) = configured_address(cw, product, network_id, "queue_address") {
+        let queue = BoringOnChainQueue::new(queue_addr, provider.clone());
+        if queue.isPaused().call().await?._0 {
+            println!("Queue {} is already paused", queue_addr);
+        } else {
+            admin_actions.push(Box::new(PauseQueue::new(queue_addr)));
+            pause_count += 1;
+        }
+    }
+
+    if let Some(accountant_addr) = configured_address(cw, product, network_id, "accountant_address")
+    {
+        let version =
+            resolve_accountant_version(cw, product, network_id, &provider, accountant_addr).await?;
+        let state = AccountantState::fetch(provider.clone(), accountant_addr, version).await?;
+        if state.is_paused {
+            println!("Accountant {} is already paused", accountant_addr);
+        } else {
+            admin_actions.push(Box::new(PauseAccountant::new(accountant_addr)));
+            pause_count += 1;
+        }
+    }
+
+    if let Some(manager_addr) = configured_address(cw, product, network_id, "manager_address") {
+        let manager = ManagerWithMerkleVerification::new(manager_addr, provider.clone());
+        if manager.isPaused().call().await?._0 {
+            println!("Manager {} is already paused", manager_addr);
+        } else {
+            admin_actions.push(Box::new(PauseManager::new(manager_addr)));
+            pause_count += 1;
+        }
+    }
+
+    if pause_count == 0 {
+        return Err(eyre!(
+            "Every contract of {} on {} is already paused",
+            product,
+            network_id
+        ));
+    }
+
+    Ok(())
+}
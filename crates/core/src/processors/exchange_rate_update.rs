@@ -0,0 +1,128 @@
+use crate::{
+    actions::{admin_action::AdminAction, update_exchange_rate_action::UpdateExchangeRate},
+    types::{accountant_state::AccountantState, config_wrapper::ConfigWrapper},
+    utils::probe::resolve_accountant_version,
+};
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::Address;
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::BlockTransactionsKind;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Exchange rate change bounds are in bps relative to 1e4.
+const BOUNDS_ONE: u128 = 10_000;
+// Max value of the uint96 exchange rate.
+const MAX_EXCHANGE_RATE: u128 = (1 << 96) - 1;
+
+// Manual exchange rate update, checked against the current accountant state so that an update
+// that would pause the accountant or move the rate more than the configured threshold is not sent
+// by accident.
+pub async fn process_exchange_rate_update(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    exchange_rate_data: &Value,
+    unpaused_in_batch: bool,
+) -> Result<()> {
+    // Exchange rates do not fit in a JSON number, so accept decimal strings too.
+    let new_rate = match &exchange_rate_data["exchange_rate"] {
+        Value::String(s) => s.parse::<u128>()?,
+        Value::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| eyre!("exchange_rate must be a positive integer"))?
+            as u128,
+        _ => return Err(eyre!("exchange_rate must be a string or a number")),
+    };
+    if new_rate == 0 || new_rate > MAX_EXCHANGE_RATE {
+        return Err(eyre!("exchange_rate must be non zero and fit in a uint96"));
+    }
+    let override_threshold = match exchange_rate_data.get("override_threshold") {
+        Some(v) => v
+            .as_bool()
+            .ok_or_else(|| eyre!("override_threshold must be a boolean"))?,
+        None => false,
+    };
+
+    let accountant_addr_str =
+        cw.get_product_config_value(product, network_id, "accountant_address")?;
+    let accountant_addr = accountant_addr_str.parse::<Address>()?;
+
+    // Query current accountant state
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let version =
+        resolve_accountant_version(cw, product, network_id, &provider, accountant_addr).await?;
+    let state = AccountantState::fetch(provider.clone(), accountant_addr, version).await?;
+    let now = provider
+        .get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes)
+        .await?
+        .ok_or_else(|| eyre!("Failed to fetch latest block"))?
+        .header
+        .timestamp;
+
+    if state.is_paused && !unpaused_in_batch {
+        return Err(eyre!(
+            "Accountant is paused, exchange rate updates revert until it is unpaused"
+        ));
+    }
+
+    // Mirror the checks the accountant makes before accepting the new rate.
+    let current_rate = state.exchange_rate;
+    let upper_limit = current_rate * state.allowed_exchange_rate_change_upper as u128 / BOUNDS_ONE;
+    let lower_limit = current_rate * state.allowed_exchange_rate_change_lower as u128 / BOUNDS_ONE;
+    let next_update = state.last_update_timestamp + state.minimum_update_delay_in_seconds as u64;
+
+    let mut pause_reasons = Vec::new();
+    if new_rate > upper_limit {
+        pause_reasons.push(format!("above the upper bound {}", upper_limit));
+    }
+    if new_rate < lower_limit {
+        pause_reasons.push(format!("below the lower bound {}", lower_limit));
+    }
+    if now < next_update {
+        pause_reasons.push(format!(
+            "sent {} seconds before the minimum update delay has passed",
+            next_update - now
+        ));
+    }
+
+    let change_bps = (new_rate as i128 - current_rate as i128) * BOUNDS_ONE as i128
+        / current_rate.max(1) as i128;
+    let threshold_bps = cw.get_exchange_rate_change_threshold_bps(network_id)?;
+
+    println!("\nExchange rate update for {} on {}", product, network_id);
+    println!("Current rate: {}", current_rate);
+    println!("New rate: {}", new_rate);
+    println!("Change: {:+.4}%", change_bps as f64 / 100.0);
+    if pause_reasons.is_empty() {
+        println!("Accountant will not pause");
+    } else {
+        println!(
+            "WARNING: accountant will pause, rate is {}",
+            pause_reasons.join(", ")
+        );
+    }
+
+    if !override_threshold {
+        if change_bps.unsigned_abs() > threshold_bps as u128 {
+            return Err(eyre!(
+                "Exchange rate change of {} bps exceeds the threshold of {} bps, set override_threshold to send it anyway",
+                change_bps,
+                threshold_bps
+            ));
+        }
+        if !pause_reasons.is_empty() {
+            return Err(eyre!(
+                "Exchange rate update would pause the accountant, set override_threshold to send it anyway"
+            ));
+        }
+    }
+
+    let action = UpdateExchangeRate::new(accountant_addr, current_rate, new_rate);
+    admin_actions.push(Box::new(action));
+
+    Ok(())
+}
@@ -1,7 +1,19 @@
+pub mod accountant_update;
 pub mod asset_update;
+pub mod emergency_pause;
+pub mod exchange_rate_update;
+pub mod owner_rotation;
+pub mod queue_update;
+pub mod raw_calls;
 pub mod roles_update;
 pub mod root_update;
+pub mod safe_modules;
+pub mod safe_owners;
+pub mod sign_message;
 pub mod solver_update;
 pub mod strategist_roles;
+pub mod teller_update;
+pub mod treasury;
 pub mod update_fees;
+pub mod vault_update;
 pub mod withdraw_asset_update;
@@ -0,0 +1,182 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, set_authority_action::SetAuthority,
+        transfer_ownership_action::TransferOwnership,
+    },
+    bindings::auth::Auth,
+    types::config_wrapper::ConfigWrapper,
+    utils::probe::{ensure_has_code, ensure_is_safe},
+};
+use alloy::primitives::Address;
+use alloy::providers::ProviderBuilder;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Config keys of the product contracts with an owner and an authority. The roles authority goes
+// last, since it controls who may call the other contracts.
+const OWNED_CONTRACT_KEYS: [&str; 6] = [
+    "vault_address",
+    "teller_address",
+    "accountant_address",
+    "manager_address",
+    "queue_address",
+    "roles_authority_address",
+];
+
+/// A product contract moved to a new owner, with its current owner and authority.
+pub struct OwnedContract {
+    pub key: String,
+    pub address: Address,
+    pub owner: Address,
+    pub authority: Address,
+    /// Whether the contract is owned by the current admin or already by the new owner, others can
+    /// not be moved by the rotation.
+    pub movable: bool,
+}
+
+// Address currently administering the product, the timelock if there is one.
+fn current_admin(cw: &ConfigWrapper, product: &str, network_id: u32) -> Result<Address> {
+    let admin = match cw.get_product_config_value(product, network_id, "timelock_address") {
+        Ok(timelock) => timelock,
+        Err(_) => cw.get_product_config_value(product, network_id, "multisig_address")?,
+    };
+    Ok(admin.parse::<Address>()?)
+}
+
+/// Every configured contract of a product with an owner, flagging the ones owned by anyone other
+/// than the current admin or `new_owner`, which a rotation can not move.
+pub async fn rotation_contracts(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    new_owner: Address,
+) -> Result<Vec<OwnedContract>> {
+    let admin = current_admin(cw, product, network_id)?;
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+
+    let mut contracts: Vec<OwnedContract> = Vec::new();
+    for key in OWNED_CONTRACT_KEYS {
+        let Some(address) = cw
+            .get_product_config_value(product, network_id, key)
+            .ok()
+            .and_then(|addr| addr.parse::<Address>().ok())
+            .filter(|addr| *addr != Address::ZERO)
+        else {
+            continue;
+        };
+        if contracts.iter().any(|c| c.address == address) {
+            continue;
+        }
+
+        let auth = Auth::new(address, provider.clone());
+        let owner = auth.owner().call().await?._0;
+        let authority = auth.authority().call().await?._0;
+        contracts.push(OwnedContract {
+            key: key.to_string(),
+            address,
+            owner,
+            authority,
+            movable: owner == admin || owner == new_owner,
+        });
+    }
+
+    Ok(contracts)
+}
+
+// Moves every product contract to a new owner, and optionally a new authority. Authorities are
+// updated first while the current admin still owns every contract, then ownership is transferred
+// with the roles authority last.
+pub async fn process_owner_rotation(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    rotation_data: &Value,
+) -> Result<()> {
+    let new_owner = rotation_data["new_owner"]
+        .as_str()
+        .ok_or_else(|| eyre!("new_owner must be a string"))?
+        .parse::<Address>()?;
+    let new_authority = match rotation_data.get("new_authority") {
+        Some(v) => Some(
+            v.as_str()
+                .ok_or_else(|| eyre!("new_authority must be a string"))?
+                .parse::<Address>()?,
+        ),
+        None => None,
+    };
+
+    // Contracts owned by someone else are only left behind if explicitly allowed.
+    let skip_foreign_owned = match rotation_data.get("skip_foreign_owned") {
+        Some(Value::Bool(skip)) => *skip,
+        Some(_) => return Err(eyre!("skip_foreign_owned must be a boolean")),
+        None => false,
+    };
+
+    if new_owner == Address::ZERO {
+        return Err(eyre!("new_owner must not be the zero address"));
+    }
+
+    // The new owner is checked to administer the product through Safe txs after the rotation.
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    ensure_is_safe(&provider, new_owner, "owner").await?;
+    if let Some(new_authority) = new_authority {
+        ensure_has_code(&provider, new_authority, "authority").await?;
+    }
+
+    let (contracts, foreign): (Vec<_>, Vec<_>) =
+        rotation_contracts(cw, product, network_id, new_owner)
+            .await?
+            .into_iter()
+            .partition(|contract| contract.movable);
+    if !foreign.is_empty() {
+        let foreign = foreign
+            .iter()
+            .map(|c| format!("{} {} owned by {}", c.key, c.address, c.owner))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !skip_foreign_owned {
+            return Err(eyre!(
+                "Rotation of {} on {} would be partial, not owned by its admin: {}",
+                product,
+                network_id,
+                foreign
+            ));
+        }
+        println!(
+            "WARNING: skipping contracts not owned by the admin: {}",
+            foreign
+        );
+    }
+    if contracts.is_empty() {
+        return Err(eyre!(
+            "No contracts of {} on {} can be moved to {}",
+            product,
+            network_id,
+            new_owner
+        ));
+    }
+
+    // Contracts without an authority are owner only, and keep it that way.
+    if let Some(new_authority) = new_authority {
+        for contract in &contracts {
+            if contract.authority != Address::ZERO && contract.authority != new_authority {
+                let action = SetAuthority::new(contract.address, new_authority);
+                admin_actions.push(Box::new(action));
+            }
+        }
+    }
+
+    for contract in &contracts {
+        if contract.owner != new_owner {
+            let action = TransferOwnership::new(contract.address, new_owner);
+            admin_actions.push(Box::new(action));
+        }
+    }
+
+    Ok(())
+}
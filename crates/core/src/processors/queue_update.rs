@@ -0,0 +1,135 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, cancel_user_withdraws_action::CancelUserWithdraws,
+        pause_queue_action::PauseQueue, unpause_queue_action::UnpauseQueue,
+    },
+    bindings::boring_queue::BoringOnChainQueue::{self, OnChainWithdraw},
+    types::config_wrapper::ConfigWrapper,
+};
+use alloy::primitives::{
+    Address,
+    aliases::{U24, U40, U96},
+    keccak256,
+};
+use alloy::providers::ProviderBuilder;
+use alloy::sol_types::SolValue;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Reads an unsigned integer given either as a JSON number or a decimal string.
+fn parse_uint(request: &Value, key: &str) -> Result<u128> {
+    match &request[key] {
+        Value::String(s) => s
+            .parse::<u128>()
+            .map_err(|e| eyre!("{} must be a positive integer: {}", key, e)),
+        Value::Number(n) => {
+            Ok(n.as_u64()
+                .ok_or_else(|| eyre!("{} must be a positive integer", key))? as u128)
+        }
+        _ => Err(eyre!("{} must be a string or a number", key)),
+    }
+}
+
+fn parse_address(request: &Value, key: &str) -> Result<Address> {
+    Ok(request[key]
+        .as_str()
+        .ok_or_else(|| eyre!("{} must be a string", key))?
+        .parse::<Address>()?)
+}
+
+// Reads an unsigned integer that must fit in one of the narrower request fields.
+fn parse_narrow_uint<T: TryFrom<u128>>(request: &Value, key: &str, bits: u32) -> Result<T> {
+    let value = parse_uint(request, key)?;
+    T::try_from(value).map_err(|_| eyre!("{} {} does not fit in a uint{}", key, value, bits))
+}
+
+// Parses a withdraw request as emitted in OnChainWithdrawRequested.
+fn parse_withdraw_request(request: &Value) -> Result<OnChainWithdraw> {
+    Ok(OnChainWithdraw {
+        nonce: parse_narrow_uint::<U96>(request, "nonce", 96)?,
+        user: parse_address(request, "user")?,
+        assetOut: parse_address(request, "asset_out")?,
+        amountOfShares: parse_uint(request, "amount_of_shares")?,
+        amountOfAssets: parse_uint(request, "amount_of_assets")?,
+        creationTime: parse_narrow_uint::<U40>(request, "creation_time", 40)?,
+        secondsToMaturity: parse_narrow_uint::<U24>(request, "seconds_to_maturity", 24)?,
+        secondsToDeadline: parse_narrow_uint::<U24>(request, "seconds_to_deadline", 24)?,
+    })
+}
+
+pub async fn process_queue_updates(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    queue_data: &Value,
+) -> Result<()> {
+    let paused = match queue_data.get("paused") {
+        Some(v) => Some(
+            v.as_bool()
+                .ok_or_else(|| eyre!("paused must be a boolean"))?,
+        ),
+        None => None,
+    };
+    let requests = match queue_data.get("cancel_user_withdraws") {
+        Some(v) => v
+            .as_array()
+            .ok_or_else(|| eyre!("cancel_user_withdraws must be an array"))?
+            .iter()
+            .map(parse_withdraw_request)
+            .collect::<Result<Vec<_>>>()?,
+        None => Vec::new(),
+    };
+
+    if paused.is_none() && requests.is_empty() {
+        return Err(eyre!(
+            "update_queue must set at least one of paused or cancel_user_withdraws"
+        ));
+    }
+
+    let queue_addr_str = cw.get_product_config_value(product, network_id, "queue_address")?;
+    let queue_addr = queue_addr_str.parse::<Address>()?;
+
+    // Query current queue state
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let queue = BoringOnChainQueue::new(queue_addr, provider);
+
+    // Pausing happens first, so that no request is solved while withdraws are being cancelled.
+    let is_paused = match paused {
+        Some(_) => queue.isPaused().call().await?._0,
+        None => false,
+    };
+    if paused == Some(true) && !is_paused {
+        admin_actions.push(Box::new(PauseQueue::new(queue_addr)));
+    }
+
+    if !requests.is_empty() {
+        // Only cancel requests that are still outstanding, the rest were already solved or
+        // cancelled.
+        let outstanding = queue.getRequestIds().call().await?._0;
+        let mut to_cancel = Vec::new();
+        for request in requests {
+            let request_id = keccak256(request.abi_encode());
+            if outstanding.contains(&request_id) {
+                to_cancel.push(request);
+            } else {
+                println!(
+                    "WARNING: withdraw request {} of {} is not outstanding, skipping it",
+                    request_id, request.user
+                );
+            }
+        }
+        if !to_cancel.is_empty() {
+            let action = CancelUserWithdraws::new(queue_addr, to_cancel);
+            admin_actions.push(Box::new(action));
+        }
+    }
+
+    if paused == Some(false) && is_paused {
+        admin_actions.push(Box::new(UnpauseQueue::new(queue_addr)));
+    }
+
+    Ok(())
+}
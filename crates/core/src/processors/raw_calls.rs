@@ -0,0 +1,154 @@
+use crate::{
+    actions::{admin_action::AdminAction, raw_call_action::RawCall},
+    types::{config_wrapper::ConfigWrapper, transaction::Operation},
+};
+use alloy::dyn_abi::{DynSolType, DynSolValue, JsonAbiExt};
+use alloy::json_abi::Function;
+use alloy::primitives::{Address, Bytes, U256};
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Converts a JSON argument to a value of the parameter type. Strings are parsed with
+// DynSolType::coerce_str, so arrays and tuples may be given as `"[a, b]"` and `"(a, b)"`, while
+// JSON arrays are converted element by element, for arrays and tuples alike.
+fn arg_to_value(ty: &DynSolType, arg: &Value) -> Result<DynSolValue> {
+    let coerce = |text: &str| {
+        ty.coerce_str(text)
+            .map_err(|e| eyre!("Invalid {} argument {}: {}", ty, text, e))
+    };
+    match (ty, arg) {
+        (_, Value::String(s)) => coerce(s),
+        (_, Value::Number(n)) => coerce(&n.to_string()),
+        (_, Value::Bool(b)) => coerce(&b.to_string()),
+        (DynSolType::Array(inner), Value::Array(items)) => Ok(DynSolValue::Array(
+            items
+                .iter()
+                .map(|item| arg_to_value(inner, item))
+                .collect::<Result<_>>()?,
+        )),
+        (DynSolType::FixedArray(inner, len), Value::Array(items)) if items.len() == *len => {
+            Ok(DynSolValue::FixedArray(
+                items
+                    .iter()
+                    .map(|item| arg_to_value(inner, item))
+                    .collect::<Result<_>>()?,
+            ))
+        }
+        (DynSolType::Tuple(types), Value::Array(items)) if items.len() == types.len() => {
+            Ok(DynSolValue::Tuple(
+                types
+                    .iter()
+                    .zip(items)
+                    .map(|(ty, item)| arg_to_value(ty, item))
+                    .collect::<Result<_>>()?,
+            ))
+        }
+        _ => Err(eyre!("Invalid {} argument {}", ty, arg)),
+    }
+}
+
+// Target is either an address or a product config key such as `teller_address`.
+fn resolve_target(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    target: &str,
+) -> Result<Address> {
+    match target.parse::<Address>() {
+        Ok(addr) => Ok(addr),
+        Err(_) => Ok(cw
+            .get_product_config_value(product, network_id, target)?
+            .parse::<Address>()?),
+    }
+}
+
+pub fn process_raw_calls(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    raw_calls: &Value,
+) -> Result<()> {
+    let raw_calls = raw_calls
+        .as_array()
+        .ok_or_else(|| eyre!("raw_calls must be an array"))?;
+
+    for raw_call in raw_calls {
+        let target_str = raw_call["target"]
+            .as_str()
+            .ok_or_else(|| eyre!("raw call target must be a string"))?;
+        let target = resolve_target(cw, product, network_id, target_str)?;
+        let signature = raw_call["signature"]
+            .as_str()
+            .ok_or_else(|| eyre!("raw call signature must be a string"))?;
+        let function = Function::parse(signature)
+            .map_err(|e| eyre!("Invalid function signature {}: {}", signature, e))?;
+
+        let args = match raw_call.get("args") {
+            Some(v) => v
+                .as_array()
+                .ok_or_else(|| eyre!("raw call args must be an array"))?
+                .clone(),
+            None => Vec::new(),
+        };
+        if args.len() != function.inputs.len() {
+            return Err(eyre!(
+                "{} expects {} arguments, got {}",
+                signature,
+                function.inputs.len(),
+                args.len()
+            ));
+        }
+
+        let values = function
+            .inputs
+            .iter()
+            .zip(&args)
+            .map(|(param, arg)| arg_to_value(&DynSolType::parse(&param.selector_type())?, arg))
+            .collect::<Result<Vec<_>>>()?;
+        let data = function
+            .abi_encode_input(&values)
+            .map_err(|e| eyre!("Failed to encode {}: {}", signature, e))?;
+
+        let value = match raw_call.get("value") {
+            Some(Value::String(s)) => s.parse::<U256>()?,
+            Some(Value::Number(n)) => U256::from(
+                n.as_u64()
+                    .ok_or_else(|| eyre!("raw call value must be a positive integer"))?,
+            ),
+            Some(_) => return Err(eyre!("raw call value must be a string or a number")),
+            None => U256::ZERO,
+        };
+        // Delegatecalls are checked against the allowlist when the Safe tx is built.
+        let operation = match raw_call.get("operation") {
+            None => Operation::Call,
+            Some(Value::String(op)) if op == "call" => Operation::Call,
+            Some(Value::String(op)) if op == "delegatecall" => Operation::DelegateCall,
+            Some(other) => {
+                return Err(eyre!(
+                    "Unknown raw call operation {}, must be call or delegatecall",
+                    other
+                ));
+            }
+        };
+        // Calls are routed through the product timelock if there is one, unless sent directly.
+        let direct = match raw_call.get("direct") {
+            None => false,
+            Some(Value::Bool(direct)) => *direct,
+            Some(_) => return Err(eyre!("raw call direct must be a boolean")),
+        };
+
+        let action = RawCall::new(
+            target,
+            value,
+            Bytes::from(data),
+            operation,
+            function.signature(),
+            args,
+            direct,
+        );
+        admin_actions.push(Box::new(action));
+    }
+
+    Ok(())
+}
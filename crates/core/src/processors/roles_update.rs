@@ -22,6 +22,7 @@ pub fn process_roles_updates(
     let roles_authority_addr_str =
         cw.get_product_config_value(product, network_id, "roles_authority_address")?;
     let roles_authority_addr = roles_authority_addr_str.parse::<Address>()?;
+    let roles = cw.get_role_registry(product, network_id)?;
 
     // Process each role update in the array
     let roles_array = roles_data
@@ -40,12 +41,8 @@ pub fn process_roles_updates(
                     .ok_or_else(|| eyre!("user must be a string"))?;
                 let user_addr = user_str.parse::<Address>()?;
 
-                let role_id = role_update["role_id"]
-                    .as_u64()
-                    .ok_or_else(|| eyre!("role_id must be a number"))?;
-                let role_id: u8 = role_id
-                    .try_into()
-                    .map_err(|_| eyre!("role_id must be between 0 and 255"))?;
+                // Roles are given by name or by id
+                let role = roles.resolve(&role_update["role_id"])?;
 
                 let enabled = role_update["enabled"]
                     .as_bool()
@@ -54,18 +51,14 @@ pub fn process_roles_updates(
                 let action = SetUserRoleAction::new(
                     roles_authority_addr,
                     user_addr,
-                    role_id,
+                    role,
                     enabled,
                 );
                 admin_actions.push(Box::new(action));
             }
             "setRoleCapability" => {
-                let role_id = role_update["role_id"]
-                    .as_u64()
-                    .ok_or_else(|| eyre!("role_id must be a number"))?;
-                let role_id: u8 = role_id
-                    .try_into()
-                    .map_err(|_| eyre!("role_id must be between 0 and 255"))?;
+                // Roles are given by name or by id
+                let role = roles.resolve(&role_update["role_id"])?;
 
                 let target_str = role_update["target_contract"]
                     .as_str()
@@ -83,7 +76,7 @@ pub fn process_roles_updates(
 
                 let action = SetRoleCapabilityAction::new(
                     roles_authority_addr,
-                    role_id,
+                    role,
                     target_addr,
                     function_signature,
                     enabled,
@@ -0,0 +1,260 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, disable_module_action::DisableModule,
+        enable_module_action::EnableModule, set_guard_action::SetGuard,
+    },
+    bindings::{guard::Guard, multisig::GnosisSafe},
+    types::{config_wrapper::ConfigWrapper, simulation_config::SimulationConfig},
+    utils::{probe::ensure_has_code, simulate::SIMULATION_MODULE},
+};
+use alloy::primitives::{Address, Bytes, FixedBytes, U256, address, b256, fixed_bytes};
+use alloy::providers::{Provider, ProviderBuilder};
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Head of the Safe modules linked list.
+const SENTINEL_MODULES: Address = address!("0x0000000000000000000000000000000000000001");
+// keccak256("guard_manager.guard.address")
+const GUARD_STORAGE_SLOT: FixedBytes<32> =
+    b256!("0x4a204f620c8c5ccdca3fd54d003badd85ba500436a431f0cbda4f558c93c34c8");
+// ERC165 interface ids of the Safe transaction guard and the Safe 1.5 module guard.
+const GUARD_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("0xe6d7a83a");
+const MODULE_GUARD_INTERFACE_ID: FixedBytes<4> = fixed_bytes!("0x58401ed8");
+const MODULES_PAGE_SIZE: u64 = 50;
+
+fn parse_addresses(modules_data: &Value, key: &str) -> Result<Vec<Address>> {
+    match modules_data.get(key) {
+        Some(v) => v
+            .as_array()
+            .ok_or_else(|| eyre!("{} must be an array", key))?
+            .iter()
+            .map(|module| {
+                Ok(module
+                    .as_str()
+                    .ok_or_else(|| eyre!("{} must contain address strings", key))?
+                    .parse::<Address>()?)
+            })
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+// Every enabled module of the Safe, in linked list order.
+async fn get_modules<P: Provider>(
+    safe: &GnosisSafe::GnosisSafeInstance<(), P>,
+) -> Result<Vec<Address>> {
+    let mut modules = Vec::new();
+    let mut start = SENTINEL_MODULES;
+    loop {
+        let page = safe
+            .getModulesPaginated(start, U256::from(MODULES_PAGE_SIZE))
+            .call()
+            .await?;
+        modules.extend(page.array);
+        if page.next == SENTINEL_MODULES || page.next == Address::ZERO {
+            return Ok(modules);
+        }
+        start = page.next;
+    }
+}
+
+// Updates the modules and guard of the product multisig. Module changes are applied to a local copy
+// of the modules list in order, enables then disables, so every prevModule matches the list at the
+// time the call is executed. The guard is set last.
+pub async fn process_safe_modules_update(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    modules_data: &Value,
+) -> Result<()> {
+    let enable = parse_addresses(modules_data, "enable")?;
+    let disable = parse_addresses(modules_data, "disable")?;
+    let guard = match modules_data.get("guard") {
+        Some(v) => Some(
+            v.as_str()
+                .ok_or_else(|| eyre!("guard must be a string"))?
+                .parse::<Address>()?,
+        ),
+        None => None,
+    };
+
+    if enable.is_empty() && disable.is_empty() && guard.is_none() {
+        return Err(eyre!(
+            "safe_modules must set at least one of enable, disable or guard"
+        ));
+    }
+
+    let safe_addr = cw
+        .get_product_config_value(product, network_id, "multisig_address")?
+        .parse::<Address>()?;
+
+    // Query current modules and guard
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let safe = GnosisSafe::new(safe_addr, provider.clone());
+    let mut modules = get_modules(&safe).await?;
+
+    for module in enable {
+        // The simulator is enabled as a module through a storage override, enabling it for real
+        // would let simulations hide what the batch actually needs.
+        if module == SIMULATION_MODULE {
+            return Err(eyre!(
+                "{} is the simulation module and can not be enabled",
+                module
+            ));
+        }
+        if module == Address::ZERO || module == SENTINEL_MODULES {
+            return Err(eyre!("{} can not be a Safe module", module));
+        }
+        if modules.contains(&module) {
+            println!("{} is already enabled, skipping it", module);
+            continue;
+        }
+        ensure_has_code(&provider, module, "module").await?;
+        // New modules are inserted at the head of the list.
+        modules.insert(0, module);
+        let action = EnableModule::new(safe_addr, module);
+        admin_actions.push(Box::new(action));
+    }
+
+    for module in disable {
+        let prev_module = match modules.iter().position(|m| *m == module) {
+            Some(0) => SENTINEL_MODULES,
+            Some(index) => modules[index - 1],
+            None => {
+                println!("{} is not enabled, skipping it", module);
+                continue;
+            }
+        };
+        modules.retain(|m| *m != module);
+        let action = DisableModule::new(safe_addr, prev_module, module);
+        admin_actions.push(Box::new(action));
+    }
+
+    if let Some(new_guard) = guard {
+        let current_guard = Address::from_word(
+            provider
+                .get_storage_at(safe_addr, GUARD_STORAGE_SLOT.into())
+                .await?
+                .into(),
+        );
+        if new_guard == current_guard {
+            println!("{} is already the guard, skipping it", new_guard);
+        } else {
+            if new_guard != Address::ZERO {
+                ensure_guard_supported(&provider, safe_addr, new_guard).await?;
+            }
+            let action = SetGuard::new(safe_addr, new_guard);
+            admin_actions.push(Box::new(action));
+        }
+    }
+
+    Ok(())
+}
+
+// Checks the guard implements the Safe guard interface, and that module txs from the simulation
+// module still go through if it is also a module guard.
+async fn ensure_guard_supported<P: Provider>(
+    provider: &P,
+    safe: Address,
+    guard: Address,
+) -> Result<()> {
+    ensure_has_code(provider, guard, "guard").await?;
+    let guard_contract = Guard::new(guard, provider);
+    if !guard_contract
+        .supportsInterface(GUARD_INTERFACE_ID)
+        .call()
+        .await?
+        ._0
+    {
+        return Err(eyre!("{} does not support the Safe guard interface", guard));
+    }
+
+    let is_module_guard = guard_contract
+        .supportsInterface(MODULE_GUARD_INTERFACE_ID)
+        .call()
+        .await
+        .map(|r| r._0)
+        .unwrap_or(false);
+    if is_module_guard
+        && guard_contract
+            .checkModuleTransaction(
+                Address::ZERO,
+                U256::ZERO,
+                Bytes::new(),
+                0,
+                SIMULATION_MODULE,
+            )
+            .from(safe)
+            .call()
+            .await
+            .is_err()
+    {
+        return Err(eyre!(
+            "Guard {} blocks module txs from the simulation module, batches could no longer be simulated",
+            guard
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks Safe txs executed after a `setGuard` call in the same batch pass the new guard, so the
+/// guard does not block the rest of the batch.
+pub async fn check_guard_allows_txs(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    safe: Address,
+    guard: Address,
+    configs: &[SimulationConfig],
+) -> Result<()> {
+    if guard == Address::ZERO || configs.is_empty() {
+        return Ok(());
+    }
+
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let guard_contract = Guard::new(guard, provider.clone());
+    // The executor of the Safe tx is not known yet, use an owner.
+    let executor = GnosisSafe::new(safe, provider)
+        .getOwners()
+        .call()
+        .await?
+        .owners
+        .first()
+        .copied()
+        .unwrap_or(Address::ZERO);
+
+    for config in configs {
+        let result = guard_contract
+            .checkTransaction(
+                config.to(),
+                config.value(),
+                config.data(),
+                config.operation,
+                U256::ZERO,
+                U256::ZERO,
+                U256::ZERO,
+                Address::ZERO,
+                Address::ZERO,
+                Bytes::new(),
+                executor,
+            )
+            .from(safe)
+            .call()
+            .await;
+        if let Err(e) = result {
+            return Err(eyre!(
+                "Guard {} blocks a Safe tx to {} made after it in the same batch: {}",
+                guard,
+                config.to,
+                e
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,194 @@
+use crate::{
+    actions::{
+        add_owner_with_threshold_action::AddOwnerWithThreshold, admin_action::AdminAction,
+        change_threshold_action::ChangeThreshold, remove_owner_action::RemoveOwner,
+        swap_owner_action::SwapOwner,
+    },
+    bindings::multisig::GnosisSafe,
+    types::config_wrapper::ConfigWrapper,
+};
+use alloy::primitives::{Address, address};
+use alloy::providers::ProviderBuilder;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Head of the Safe owners linked list.
+const SENTINEL_OWNERS: Address = address!("0x0000000000000000000000000000000000000001");
+
+fn parse_addresses(owners_data: &Value, key: &str) -> Result<Vec<Address>> {
+    match owners_data.get(key) {
+        Some(v) => v
+            .as_array()
+            .ok_or_else(|| eyre!("{} must be an array", key))?
+            .iter()
+            .map(|owner| {
+                Ok(owner
+                    .as_str()
+                    .ok_or_else(|| eyre!("{} must contain address strings", key))?
+                    .parse::<Address>()?)
+            })
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn ensure_valid_owner(owner: Address) -> Result<()> {
+    if owner == Address::ZERO || owner == SENTINEL_OWNERS {
+        return Err(eyre!("{} can not be a Safe owner", owner));
+    }
+    Ok(())
+}
+
+// Owner pointing to `owner` in the linked list, `owners` being in getOwners order.
+fn prev_owner(owners: &[Address], owner: Address) -> Result<Address> {
+    match owners.iter().position(|o| *o == owner) {
+        Some(0) => Ok(SENTINEL_OWNERS),
+        Some(index) => Ok(owners[index - 1]),
+        None => Err(eyre!("{} is not an owner of the Safe", owner)),
+    }
+}
+
+// Updates the owners of the product multisig. Changes are applied to a local copy of the owners
+// list in order, swaps then adds then removes, so every prevOwner matches the list at the time the
+// call is executed.
+pub async fn process_safe_owners_update(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    owners_data: &Value,
+) -> Result<()> {
+    let add = parse_addresses(owners_data, "add")?;
+    let remove = parse_addresses(owners_data, "remove")?;
+    let swap = match owners_data.get("swap") {
+        Some(v) => v
+            .as_array()
+            .ok_or_else(|| eyre!("swap must be an array"))?
+            .iter()
+            .map(|s| {
+                let old = s["old"]
+                    .as_str()
+                    .ok_or_else(|| eyre!("swap old must be a string"))?
+                    .parse::<Address>()?;
+                let new = s["new"]
+                    .as_str()
+                    .ok_or_else(|| eyre!("swap new must be a string"))?
+                    .parse::<Address>()?;
+                Ok((old, new))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => Vec::new(),
+    };
+    let threshold = match owners_data.get("threshold") {
+        Some(v) => Some(
+            v.as_u64()
+                .ok_or_else(|| eyre!("threshold must be a number"))?,
+        ),
+        None => None,
+    };
+
+    if add.is_empty() && remove.is_empty() && swap.is_empty() && threshold.is_none() {
+        return Err(eyre!(
+            "safe_owners must set at least one of add, remove, swap or threshold"
+        ));
+    }
+
+    let safe_addr = cw
+        .get_product_config_value(product, network_id, "multisig_address")?
+        .parse::<Address>()?;
+
+    // Query current owners and threshold
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let safe = GnosisSafe::new(safe_addr, provider);
+    let mut owners = safe.getOwners().call().await?.owners;
+    let current_threshold = safe.getThreshold().call().await?.threshold.to::<u64>();
+
+    let target_threshold = threshold.unwrap_or(current_threshold);
+    let final_owner_count = (owners.len() + add.len()).saturating_sub(remove.len());
+    if target_threshold == 0 || target_threshold as usize > final_owner_count {
+        return Err(eyre!(
+            "Threshold {} is not reachable with {} owners",
+            target_threshold,
+            final_owner_count
+        ));
+    }
+
+    for (old_owner, new_owner) in swap {
+        ensure_valid_owner(new_owner)?;
+        if owners.contains(&new_owner) {
+            return Err(eyre!("{} is already an owner of the Safe", new_owner));
+        }
+        let prev = prev_owner(&owners, old_owner)?;
+        let index = owners.iter().position(|o| *o == old_owner).unwrap();
+        owners[index] = new_owner;
+        let action = SwapOwner::new(safe_addr, prev, old_owner, new_owner);
+        admin_actions.push(Box::new(action));
+    }
+
+    // Thresholds of adds and removes never exceed the owner count at that point, so the Safe stays
+    // executable after every call.
+    let mut threshold_now = current_threshold;
+    for owner in add {
+        ensure_valid_owner(owner)?;
+        if owners.contains(&owner) {
+            return Err(eyre!("{} is already an owner of the Safe", owner));
+        }
+        // New owners are inserted at the head of the list.
+        owners.insert(0, owner);
+        threshold_now = target_threshold.min(owners.len() as u64);
+        let action = AddOwnerWithThreshold::new(safe_addr, owner, threshold_now);
+        admin_actions.push(Box::new(action));
+    }
+
+    for owner in remove {
+        let prev = prev_owner(&owners, owner)?;
+        owners.retain(|o| *o != owner);
+        threshold_now = target_threshold.min(owners.len() as u64);
+        let action = RemoveOwner::new(safe_addr, prev, owner, threshold_now);
+        admin_actions.push(Box::new(action));
+    }
+
+    if threshold_now != target_threshold {
+        let action = ChangeThreshold::new(safe_addr, target_threshold);
+        admin_actions.push(Box::new(action));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OWNERS: [Address; 3] = [
+        address!("0x000000000000000000000000000000000000000a"),
+        address!("0x000000000000000000000000000000000000000b"),
+        address!("0x000000000000000000000000000000000000000c"),
+    ];
+
+    #[test]
+    fn prev_owner_of_the_first_owner_is_the_sentinel() {
+        assert_eq!(prev_owner(&OWNERS, OWNERS[0]).unwrap(), SENTINEL_OWNERS);
+    }
+
+    #[test]
+    fn prev_owner_is_the_owner_listed_before() {
+        assert_eq!(prev_owner(&OWNERS, OWNERS[1]).unwrap(), OWNERS[0]);
+        assert_eq!(prev_owner(&OWNERS, OWNERS[2]).unwrap(), OWNERS[1]);
+    }
+
+    #[test]
+    fn prev_owner_rejects_non_owners() {
+        assert!(prev_owner(&OWNERS, SENTINEL_OWNERS).is_err());
+        assert!(prev_owner(&[], OWNERS[0]).is_err());
+    }
+
+    #[test]
+    fn owners_can_not_be_the_zero_address_or_the_sentinel() {
+        assert!(ensure_valid_owner(Address::ZERO).is_err());
+        assert!(ensure_valid_owner(SENTINEL_OWNERS).is_err());
+        assert!(ensure_valid_owner(OWNERS[0]).is_ok());
+    }
+}
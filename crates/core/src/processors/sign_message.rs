@@ -0,0 +1,29 @@
+use crate::{
+    actions::{admin_action::AdminAction, sign_message_action::SignMessage},
+    types::config_wrapper::ConfigWrapper,
+};
+use alloy::primitives::{Address, Bytes};
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Marks a message as signed by the multisig through a delegatecall into SignMessageLib.
+pub fn process_sign_message(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    network_id: u32,
+    sign_message_data: &Value,
+) -> Result<()> {
+    let message_str = sign_message_data["message"]
+        .as_str()
+        .ok_or_else(|| eyre!("message must be a hex string"))?;
+    let message = message_str.parse::<Bytes>()?;
+
+    let sign_message_lib = cw
+        .get_sign_message_lib_address(network_id)?
+        .parse::<Address>()?;
+
+    let action = SignMessage::new(sign_message_lib, message);
+    admin_actions.push(Box::new(action));
+
+    Ok(())
+}
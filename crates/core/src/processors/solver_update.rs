@@ -10,12 +10,14 @@ use alloy::primitives::Address;
 use eyre::{Result, eyre};
 use serde_json::Value;
 
+// Template used when neither the action file nor the product config picks one.
+const DEFAULT_SOLVER_TEMPLATE: &str = "v1";
+
 enum Mode {
     Setup,
     TearDown,
 }
 // TODO this function could read state to se if it needs to update the fee or not.
-// TODO old products like liquid eth have different functions so this will fail
 pub fn process_solver_update(
     admin_actions: &mut Vec<Box<dyn AdminAction>>,
     cw: &ConfigWrapper,
@@ -28,13 +30,13 @@ pub fn process_solver_update(
     let tear_down = solver_data.get("tear_down");
     let mode;
 
-    if setup.is_some() && tear_down.is_some() {
+    if let (Some(setup), Some(tear_down)) = (setup, tear_down) {
         // Make sure one is false.
-        let setup = match setup.unwrap().as_bool() {
+        let setup = match setup.as_bool() {
             Some(val) => val,
             None => return Err(eyre::eyre!("setup must be a bool")),
         };
-        let tear_down = match tear_down.unwrap().as_bool() {
+        let tear_down = match tear_down.as_bool() {
             Some(val) => val,
             None => return Err(eyre::eyre!("tear_down must be a bool")),
         };
@@ -87,6 +89,18 @@ pub fn process_solver_update(
         None => false,
     };
 
+    // Solver generation, from the action file, then the product config, then the default one.
+    let template_name = match solver_data.get("template") {
+        Some(v) => v
+            .as_str()
+            .ok_or_else(|| eyre!("template must be a string"))?
+            .to_string(),
+        None => cw
+            .get_product_config_value(product, network_id, "solver_template")
+            .unwrap_or_else(|_| DEFAULT_SOLVER_TEMPLATE.to_string()),
+    };
+    let template = cw.get_solver_template(&template_name, product, network_id)?;
+
     // Get roles_authority address for the product
     let roles_authority_addr_str =
         cw.get_product_config_value(product, network_id, "roles_authority_address")?;
@@ -97,51 +111,53 @@ pub fn process_solver_update(
         Mode::TearDown => false,
     };
 
-    // Add action so boring queue can call boringSolve
-    let action = SetRoleCapabilityAction::new(
-        roles_authority_addr,
-        32,
-        solver_addr,
-        "boringSolve(address,address,address,uint256,uint256,bytes)".to_string(),
-        enabled,
-    );
-    admin_actions.push(Box::new(action));
-    // Allow solver eoa to call solve functions.
-    let action = SetRoleCapabilityAction::new(
-                roles_authority_addr,
-                33,
-                solver_addr,
-                "boringRedeemSolve((uint96,address,address,uint128,uint128,uint40,uint24,uint24)[],address,bool)".to_string(),
-                enabled,
-            );
-    admin_actions.push(Box::new(action));
-    let action = SetRoleCapabilityAction::new(
-                roles_authority_addr,
-                33,
-                solver_addr,
-                "boringRedeemMintSolve((uint96,address,address,uint128,uint128,uint40,uint24,uint24)[],address,address,address,bool)".to_string(),
-                enabled,
-            );
-    admin_actions.push(Box::new(action));
-    // Grant required roles to new solver contract
-    let action = SetUserRoleAction::new(roles_authority_addr, solver_addr, 31, enabled);
-    admin_actions.push(Box::new(action));
-    let action = SetUserRoleAction::new(roles_authority_addr, solver_addr, 12, enabled);
-    admin_actions.push(Box::new(action));
-
     if !enabled && allow_self_solves {
         return Err(eyre!(
             "Tearing down a solver and enabling self solves does not make sense"
         ));
     }
 
-    // Make self solve functions public if need be.
-    if allow_self_solves {
-        let action = SetPublicCapabilityAction::new(roles_authority_addr, solver_addr, "boringRedeemSelfSolve((uint96,address,address,uint128,uint128,uint40,uint24,uint24),address)".to_string(), true);
-        admin_actions.push(Box::new(action));
-        let action = SetPublicCapabilityAction::new(roles_authority_addr, solver_addr, "boringRedeemMintSelfSolve((uint96,address,address,uint128,uint128,uint40,uint24,uint24),address,address,address)".to_string(), true);
-        admin_actions.push(Box::new(action));
+    // Self solve functions are only made public if allowed, but always revoked in tear downs since
+    // the setup may have allowed them and revoking an unset capability is harmless.
+    let mut public_capabilities = template.public_capabilities;
+    if allow_self_solves || !enabled {
+        public_capabilities.extend(template.self_solve_capabilities);
+    }
+
+    let mut actions: Vec<Box<dyn AdminAction>> = Vec::new();
+    // Allow the queue and solver eoas to call solve functions.
+    for (role, signature) in template.role_capabilities {
+        actions.push(Box::new(SetRoleCapabilityAction::new(
+            roles_authority_addr,
+            role,
+            solver_addr,
+            signature,
+            enabled,
+        )));
+    }
+    // Grant required roles to new solver contract
+    for role in template.user_roles {
+        actions.push(Box::new(SetUserRoleAction::new(
+            roles_authority_addr,
+            solver_addr,
+            role,
+            enabled,
+        )));
+    }
+    for signature in public_capabilities {
+        actions.push(Box::new(SetPublicCapabilityAction::new(
+            roles_authority_addr,
+            solver_addr,
+            signature,
+            enabled,
+        )));
+    }
+
+    // Tear downs are the exact inverse of the setup, every grant revoked in reverse order.
+    if !enabled {
+        actions.reverse();
     }
+    admin_actions.extend(actions);
 
     Ok(())
 }
@@ -1,8 +1,10 @@
 use crate::{
     actions::{admin_action::AdminAction, set_user_role_action::SetUserRoleAction, set_merkle_root_action::SetMerkleRoot},
-    types::config_wrapper::ConfigWrapper,
+    bindings::manager::ManagerWithMerkleVerification,
+    types::{config_wrapper::ConfigWrapper, role_registry::Role},
 };
 use alloy::primitives::{Address, FixedBytes};
+use alloy::providers::ProviderBuilder;
 use eyre::{eyre, Result};
 use serde_json::Value;
 
@@ -12,7 +14,42 @@ enum StrategistUpdateMode {
     RevokeRoles,
 }
 
-pub fn process_strategist_roles_update(
+// Root currently set for the configured strategists of a product, ignoring `strategist` itself.
+// Returns None if no strategist has a root set yet.
+async fn current_product_root(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    manager_addr: Address,
+    strategist: Address,
+) -> Result<Option<FixedBytes<32>>> {
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let manager = ManagerWithMerkleVerification::new(manager_addr, provider);
+
+    let mut current_root: Option<FixedBytes<32>> = None;
+    for other_str in cw.get_product_strategists(product, network_id)? {
+        let other = other_str.parse::<Address>()?;
+        if other == strategist {
+            continue;
+        }
+        let root = manager.manageRoot(other).call().await?._0;
+        if root == FixedBytes::<32>::ZERO {
+            continue;
+        }
+        match current_root {
+            Some(existing) if existing != root => {
+                return Err(eyre!("Strategists of {} have different roots, pass the root explicitly", product));
+            }
+            _ => current_root = Some(root),
+        }
+    }
+
+    Ok(current_root)
+}
+
+pub async fn process_strategist_roles_update(
     admin_actions: &mut Vec<Box<dyn AdminAction>>,
     cw: &ConfigWrapper,
     product: &str,
@@ -41,22 +78,17 @@ pub fn process_strategist_roles_update(
     // Extract roles to add/revoke
     let roles_val = strategist_update_data
         .get("roles") // Changed from "roles_to_revoke" to be generic
-        .ok_or_else(|| eyre!("'roles' field (array of role IDs) is required"))?;
+        .ok_or_else(|| eyre!("'roles' field (array of role names or IDs) is required"))?;
     
     let roles_array = roles_val
         .as_array()
-        .ok_or_else(|| eyre!("'roles' must be an array of role IDs"))?;
+        .ok_or_else(|| eyre!("'roles' must be an array of role names or IDs"))?;
 
-    let mut role_ids: Vec<u8> = Vec::new();
+    // Roles are given by name or by id
+    let roles = cw.get_role_registry(product, network_id)?;
+    let mut role_ids: Vec<Role> = Vec::new();
     for role_val in roles_array {
-        let role_id_u64 = role_val
-            .as_u64()
-            .ok_or_else(|| eyre!("Each role ID in 'roles' must be a number"))?;
-        
-        let role_id: u8 = role_id_u64
-            .try_into()
-            .map_err(|_| eyre!(format!("Role ID {} is out of range. Must be between 0 and 255", role_id_u64)))?;
-        role_ids.push(role_id);
+        role_ids.push(roles.resolve(role_val)?);
     }
 
     if role_ids.is_empty() && !matches!(mode, StrategistUpdateMode::RevokeRoles) {
@@ -103,5 +135,34 @@ pub fn process_strategist_roles_update(
         admin_actions.push(Box::new(set_root_action));
     }
 
+    // If adding roles, optionally set the Merkle root, "current" copying the root of the other strategists
+    if matches!(mode, StrategistUpdateMode::AddRoles) && let Some(root_val) = strategist_update_data.get("root") {
+        let root_str = root_val
+            .as_str()
+            .ok_or_else(|| eyre!("'root' must be a string"))?;
+
+        let manager_addr_str =
+            cw.get_product_config_value(product, network_id, "manager_address")?;
+        let manager_addr = manager_addr_str.parse::<Address>()?;
+
+        let root = if root_str == "current" {
+            current_product_root(cw, product, network_id, manager_addr, strategist_addr).await?
+        } else {
+            Some(root_str.parse::<FixedBytes<32>>()?)
+        };
+
+        match root {
+            Some(root) => {
+                let set_root_action = SetMerkleRoot::new(
+                    manager_addr,
+                    strategist_addr,
+                    root,
+                );
+                admin_actions.push(Box::new(set_root_action));
+            }
+            None => println!("WARNING: no strategist of {} has a root set, {} gets no root", product, strategist_addr),
+        }
+    }
+
     Ok(())
 } 
\ No newline at end of file
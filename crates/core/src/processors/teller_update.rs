@@ -0,0 +1,190 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction,
+        pause_teller_action::PauseTeller,
+        set_deposit_cap_action::SetDepositCap,
+        set_share_lock_period_action::SetShareLockPeriod,
+        unpause_teller_action::UnpauseTeller,
+        update_teller_deny_list_action::{DenyList, UpdateTellerDenyList},
+    },
+    bindings::teller::TellerWithMultiAssetSupport,
+    types::{config_wrapper::ConfigWrapper, contract_versions::TellerVersion},
+    utils::probe::resolve_teller_version,
+};
+use alloy::primitives::Address;
+use alloy::providers::ProviderBuilder;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Share lock period enforced by the teller.
+const MAX_SHARE_LOCK_PERIOD: u64 = 3 * 24 * 60 * 60;
+// Max value of the uint112 deposit cap.
+const MAX_DEPOSIT_CAP: u128 = (1 << 112) - 1;
+
+// Parses the `deny` or `allow` section, returning every (list, user) pair it contains.
+fn parse_deny_list_section(section: &Value, name: &str) -> Result<Vec<(DenyList, Address)>> {
+    let mut result = Vec::new();
+    let Some(section) = section.get(name) else {
+        return Ok(result);
+    };
+    let section = section
+        .as_object()
+        .ok_or_else(|| eyre!("{} must be an object", name))?;
+    for (key, users) in section {
+        let list = match key.as_str() {
+            "from" => DenyList::From,
+            "to" => DenyList::To,
+            "operator" => DenyList::Operator,
+            _ => {
+                return Err(eyre!(
+                    "Unknown {} list {}, must be from, to or operator",
+                    name,
+                    key
+                ));
+            }
+        };
+        let users = users
+            .as_array()
+            .ok_or_else(|| eyre!("{}.{} must be an array", name, key))?;
+        for user in users {
+            let user = user
+                .as_str()
+                .ok_or_else(|| eyre!("{}.{} must contain address strings", name, key))?
+                .parse::<Address>()?;
+            result.push((list, user));
+        }
+    }
+    Ok(result)
+}
+
+pub async fn process_teller_updates(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    teller_data: &Value,
+) -> Result<()> {
+    let paused = match teller_data.get("paused") {
+        Some(v) => Some(
+            v.as_bool()
+                .ok_or_else(|| eyre!("paused must be a boolean"))?,
+        ),
+        None => None,
+    };
+    let share_lock_period = match teller_data.get("share_lock_period") {
+        Some(v) => {
+            let period = v
+                .as_u64()
+                .ok_or_else(|| eyre!("share_lock_period must be a number"))?;
+            if period > MAX_SHARE_LOCK_PERIOD {
+                return Err(eyre!(
+                    "share_lock_period must be at most {} seconds",
+                    MAX_SHARE_LOCK_PERIOD
+                ));
+            }
+            Some(period)
+        }
+        None => None,
+    };
+    // Deposit caps do not fit in a JSON number, so accept decimal strings too.
+    let deposit_cap = match teller_data.get("deposit_cap") {
+        Some(Value::String(s)) => Some(s.parse::<u128>()?),
+        Some(Value::Number(n)) => Some(
+            n.as_u64()
+                .ok_or_else(|| eyre!("deposit_cap must be a positive integer"))?
+                as u128,
+        ),
+        Some(_) => return Err(eyre!("deposit_cap must be a string or a number")),
+        None => None,
+    };
+    if deposit_cap.is_some_and(|cap| cap > MAX_DEPOSIT_CAP) {
+        return Err(eyre!("deposit_cap must fit in a uint112"));
+    }
+    let deny = parse_deny_list_section(teller_data, "deny")?;
+    let allow = parse_deny_list_section(teller_data, "allow")?;
+
+    if let Some((list, user)) = deny.iter().find(|d| allow.contains(d)) {
+        return Err(eyre!(
+            "{} is both denied and allowed in the {} list",
+            user,
+            list.as_str()
+        ));
+    }
+
+    if paused.is_none()
+        && share_lock_period.is_none()
+        && deposit_cap.is_none()
+        && deny.is_empty()
+        && allow.is_empty()
+    {
+        return Err(eyre!(
+            "update_teller must set at least one of paused, share_lock_period, deposit_cap, deny or allow"
+        ));
+    }
+
+    let teller_addr_str = cw.get_product_config_value(product, network_id, "teller_address")?;
+    let teller_addr = teller_addr_str.parse::<Address>()?;
+
+    // Query current teller state
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let version = resolve_teller_version(cw, product, network_id, &provider, teller_addr).await?;
+    let teller = TellerWithMultiAssetSupport::new(teller_addr, provider);
+
+    if version == TellerVersion::V1
+        && (deposit_cap.is_some() || !deny.is_empty() || !allow.is_empty())
+    {
+        return Err(eyre!(
+            "Legacy teller interface does not support deposit caps or deny lists"
+        ));
+    }
+
+    if let Some(new_period) = share_lock_period
+        && new_period != teller.shareLockPeriod().call().await?._0
+    {
+        let action = SetShareLockPeriod::new(teller_addr, new_period);
+        admin_actions.push(Box::new(action));
+    }
+
+    if let Some(new_cap) = deposit_cap
+        && new_cap != teller.depositCap().call().await?._0.to::<u128>()
+    {
+        let action = SetDepositCap::new(teller_addr, new_cap);
+        admin_actions.push(Box::new(action));
+    }
+
+    for (list, user, should_deny) in deny
+        .into_iter()
+        .map(|(list, user)| (list, user, true))
+        .chain(allow.into_iter().map(|(list, user)| (list, user, false)))
+    {
+        let current = teller.beforeTransferData(user).call().await?;
+        let is_denied = match list {
+            DenyList::From => current.denyFrom,
+            DenyList::To => current.denyTo,
+            DenyList::Operator => current.denyOperator,
+        };
+        if is_denied != should_deny {
+            let action = UpdateTellerDenyList::new(teller_addr, list, user, should_deny);
+            admin_actions.push(Box::new(action));
+        }
+    }
+
+    // Pause state is changed last, so that unpausing happens after the other settings are updated.
+    let is_paused = match paused {
+        Some(_) => teller.isPaused().call().await?._0,
+        None => false,
+    };
+    match paused {
+        Some(true) if !is_paused => {
+            admin_actions.push(Box::new(PauseTeller::new(teller_addr)));
+        }
+        Some(false) if is_paused => {
+            admin_actions.push(Box::new(UnpauseTeller::new(teller_addr)));
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
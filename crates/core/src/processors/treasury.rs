@@ -0,0 +1,222 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, erc20_approve_action::Erc20Approve,
+        erc20_transfer_action::Erc20Transfer, native_transfer_action::NativeTransfer,
+    },
+    bindings::erc20::ERC20,
+    types::config_wrapper::ConfigWrapper,
+};
+use alloy::primitives::{
+    Address, U256,
+    utils::{ParseUnits, parse_units},
+};
+use alloy::providers::{Provider, ProviderBuilder};
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+// Amounts are given in human units, either as a string such as "1.5" or a JSON integer. Fractional
+// JSON numbers are rejected since they were already rounded to a float when the file was parsed.
+fn parse_amount(entry: &Value, decimals: u8) -> Result<U256> {
+    let amount = match &entry["amount"] {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) if n.is_u64() => n.to_string(),
+        Value::Number(n) if n.is_i64() => {
+            return Err(eyre!("Invalid amount {}: must not be negative", n));
+        }
+        Value::Number(n) => {
+            return Err(eyre!(
+                "Invalid amount {}: fractional amounts must be given as a string",
+                n
+            ));
+        }
+        _ => return Err(eyre!("amount must be a string or a number")),
+    };
+    // parse_units silently truncates extra decimals, which could turn an approval into a revoke.
+    let fraction_digits = amount
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len());
+    if fraction_digits > decimals as usize {
+        return Err(eyre!(
+            "Invalid amount {}: the token only has {} decimals",
+            amount,
+            decimals
+        ));
+    }
+    match parse_units(&amount, decimals).map_err(|e| eyre!("Invalid amount {}: {}", amount, e))? {
+        ParseUnits::U256(amount) => Ok(amount),
+        ParseUnits::I256(_) => Err(eyre!("Invalid amount {}: must not be negative", amount)),
+    }
+}
+
+fn parse_address(entry: &Value, key: &str) -> Result<Address> {
+    Ok(entry[key]
+        .as_str()
+        .ok_or_else(|| eyre!("{} must be a string", key))?
+        .parse::<Address>()?)
+}
+
+fn warn_if_not_allowlisted(allowlist: &[Address], addr: Address, role: &str) {
+    if !allowlist.contains(&addr) {
+        println!(
+            "WARNING: {} {} is not in transfer_recipient_allowlist",
+            role, addr
+        );
+    }
+}
+
+// Transfers native tokens or ERC20s out of the product multisig. Entries use the token "native"
+// for native transfers.
+pub async fn process_transfers(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    transfers: &Value,
+) -> Result<()> {
+    let transfers = transfers
+        .as_array()
+        .ok_or_else(|| eyre!("transfers must be an array"))?;
+
+    let multisig = cw
+        .get_product_config_value(product, network_id, "multisig_address")?
+        .parse::<Address>()?;
+    let allowlist = cw.get_transfer_recipient_allowlist(network_id)?;
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+
+    for transfer in transfers {
+        let to = parse_address(transfer, "to")?;
+        if to == Address::ZERO {
+            return Err(eyre!("Transfers to the zero address are not allowed"));
+        }
+        warn_if_not_allowlisted(&allowlist, to, "Recipient");
+
+        let token = transfer["token"]
+            .as_str()
+            .ok_or_else(|| eyre!("token must be a string"))?;
+        if token == "native" {
+            let amount = parse_amount(transfer, 18)?;
+            if amount.is_zero() {
+                return Err(eyre!("Transfer amounts must be non zero"));
+            }
+            let balance = provider.get_balance(multisig).await?;
+            if balance < amount {
+                println!(
+                    "WARNING: multisig {} only holds {} wei of the native token",
+                    multisig, balance
+                );
+            }
+            let action = NativeTransfer::new(to, amount, cw.get_native_token_symbol(network_id));
+            admin_actions.push(Box::new(action));
+        } else {
+            let token = token.parse::<Address>()?;
+            let erc20 = ERC20::new(token, provider.clone());
+            let decimals = erc20.decimals().call().await?._0;
+            let symbol = erc20.symbol().call().await?._0;
+            let amount = parse_amount(transfer, decimals)?;
+            if amount.is_zero() {
+                return Err(eyre!("Transfer amounts must be non zero"));
+            }
+            let balance = erc20.balanceOf(multisig).call().await?._0;
+            if balance < amount {
+                println!(
+                    "WARNING: multisig {} only holds {} of {} {}",
+                    multisig, balance, amount, symbol
+                );
+            }
+            let action = Erc20Transfer::new(token, to, amount, symbol, decimals);
+            admin_actions.push(Box::new(action));
+        }
+    }
+
+    Ok(())
+}
+
+// Approves spenders on ERC20s held by the product multisig, skipping approvals that already match
+// the current allowance.
+pub async fn process_approvals(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    approvals: &Value,
+) -> Result<()> {
+    let approvals = approvals
+        .as_array()
+        .ok_or_else(|| eyre!("approvals must be an array"))?;
+
+    let multisig = cw
+        .get_product_config_value(product, network_id, "multisig_address")?
+        .parse::<Address>()?;
+    let allowlist = cw.get_transfer_recipient_allowlist(network_id)?;
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+
+    for approval in approvals {
+        let spender = parse_address(approval, "spender")?;
+        warn_if_not_allowlisted(&allowlist, spender, "Spender");
+
+        let token = parse_address(approval, "token")?;
+        let erc20 = ERC20::new(token, provider.clone());
+        let decimals = erc20.decimals().call().await?._0;
+        let symbol = erc20.symbol().call().await?._0;
+        // A zero amount revokes the approval.
+        let amount = parse_amount(approval, decimals)?;
+        let current = erc20.allowance(multisig, spender).call().await?._0;
+        if current == amount {
+            println!(
+                "Allowance of {} on {} is already {}, skipping it",
+                spender, symbol, amount
+            );
+            continue;
+        }
+        let action = Erc20Approve::new(token, spender, amount, symbol, decimals);
+        admin_actions.push(Box::new(action));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn amount(amount: Value, decimals: u8) -> Result<U256> {
+        parse_amount(&json!({ "amount": amount }), decimals)
+    }
+
+    #[test]
+    fn parses_human_amounts() {
+        assert_eq!(
+            amount(json!("1.5"), 18).unwrap(),
+            U256::from(1_500_000_000_000_000_000u128)
+        );
+        assert_eq!(amount(json!(" 2 "), 6).unwrap(), U256::from(2_000_000));
+        assert_eq!(amount(json!("0.000001"), 6).unwrap(), U256::from(1));
+        assert_eq!(amount(json!(3), 6).unwrap(), U256::from(3_000_000));
+        assert_eq!(amount(json!("0"), 18).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn rejects_negative_amounts() {
+        assert!(amount(json!("-1"), 18).is_err());
+        assert!(amount(json!(-1), 18).is_err());
+    }
+
+    #[test]
+    fn rejects_more_decimals_than_the_token() {
+        assert!(amount(json!("0.0000001"), 6).is_err());
+        assert!(amount(json!("1.5"), 0).is_err());
+    }
+
+    #[test]
+    fn rejects_fractional_json_numbers_and_other_types() {
+        assert!(amount(json!(1.5), 18).is_err());
+        assert!(amount(json!(true), 18).is_err());
+        assert!(amount(Value::Null, 18).is_err());
+        assert!(amount(json!("abc"), 18).is_err());
+    }
+}
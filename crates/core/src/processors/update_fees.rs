@@ -1,17 +1,19 @@
 use crate::{
     actions::{
-        admin_action::AdminAction, update_performance_fee_action::UpdatePerformanceFee,
+        admin_action::AdminAction, update_management_fee_action::UpdateManagementFee,
+        update_performance_fee_action::UpdatePerformanceFee,
         update_platform_fee_action::UpdatePlatformFee,
     },
-    types::config_wrapper::ConfigWrapper,
+    types::{config_wrapper::ConfigWrapper, contract_versions::AccountantVersion},
+    utils::probe::resolve_accountant_version,
 };
 use alloy::primitives::Address;
+use alloy::providers::ProviderBuilder;
 use eyre::Result;
 use serde_json::Value;
 
 // TODO this function could read state to se if it needs to update the fee or not.
-// TODO old products like liquid eth have different functions so this will fail
-pub fn process_fee_updates(
+pub async fn process_fee_updates(
     admin_actions: &mut Vec<Box<dyn AdminAction>>,
     cw: &ConfigWrapper,
     product: &str,
@@ -38,14 +40,33 @@ pub fn process_fee_updates(
         cw.get_product_config_value(product, network_id, "accountant_address")?;
     let accountant_addr = accountant_addr_str.parse::<Address>()?;
 
-    // Add updatePlatformFee action if Some
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let version =
+        resolve_accountant_version(cw, product, network_id, &provider, accountant_addr).await?;
+
+    // Add platform fee action if Some, legacy accountants call it the management fee.
     if let Some(new_fee) = platform_fee {
-        let action = UpdatePlatformFee::new(accountant_addr, new_fee);
-        admin_actions.push(Box::new(action));
+        match version {
+            AccountantVersion::V2 => {
+                let action = UpdatePlatformFee::new(accountant_addr, new_fee);
+                admin_actions.push(Box::new(action));
+            }
+            AccountantVersion::V1 => {
+                let action = UpdateManagementFee::new(accountant_addr, new_fee);
+                admin_actions.push(Box::new(action));
+            }
+        }
     }
 
     // Add updatePerformanceFee action if Some
     if let Some(new_fee) = performance_fee {
+        if version == AccountantVersion::V1 {
+            return Err(eyre::eyre!(
+                "Legacy accountant interface does not support a performance fee"
+            ));
+        }
         let action = UpdatePerformanceFee::new(accountant_addr, new_fee);
         admin_actions.push(Box::new(action));
     }
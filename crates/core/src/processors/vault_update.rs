@@ -0,0 +1,104 @@
+use crate::{
+    actions::{
+        admin_action::AdminAction, set_authority_action::SetAuthority,
+        set_before_transfer_hook_action::SetBeforeTransferHook,
+        transfer_ownership_action::TransferOwnership,
+    },
+    bindings::{auth::Auth, boring_vault::BoringVault},
+    types::config_wrapper::ConfigWrapper,
+    utils::probe::ensure_has_code,
+};
+use alloy::primitives::Address;
+use alloy::providers::ProviderBuilder;
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+fn parse_address(vault_data: &Value, key: &str) -> Result<Option<Address>> {
+    match vault_data.get(key) {
+        Some(v) => Ok(Some(
+            v.as_str()
+                .ok_or_else(|| eyre!("{} must be a string", key))?
+                .parse::<Address>()?,
+        )),
+        None => Ok(None),
+    }
+}
+
+pub async fn process_vault_updates(
+    admin_actions: &mut Vec<Box<dyn AdminAction>>,
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    vault_data: &Value,
+) -> Result<()> {
+    let hook = parse_address(vault_data, "before_transfer_hook")?;
+    let authority = parse_address(vault_data, "authority")?;
+    let owner = parse_address(vault_data, "owner")?;
+
+    if hook.is_none() && authority.is_none() && owner.is_none() {
+        return Err(eyre!(
+            "update_vault must set at least one of before_transfer_hook, authority or owner"
+        ));
+    }
+
+    let vault_addr_str = cw.get_product_config_value(product, network_id, "vault_address")?;
+    let vault_addr = vault_addr_str.parse::<Address>()?;
+
+    // Query current vault state
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    let vault = BoringVault::new(vault_addr, provider.clone());
+    let vault_auth = Auth::new(vault_addr, provider.clone());
+
+    if let Some(new_hook) = hook
+        && new_hook != vault.hook().call().await?._0
+    {
+        // The zero address removes the hook, anything else must be a contract.
+        if new_hook == Address::ZERO {
+            println!(
+                "WARNING: removing the before transfer hook of {}, share transfers will no longer be checked",
+                vault_addr
+            );
+        } else {
+            ensure_has_code(&provider, new_hook, "before transfer hook").await?;
+        }
+        let action = SetBeforeTransferHook::new(vault_addr, new_hook);
+        admin_actions.push(Box::new(action));
+    }
+
+    if let Some(new_authority) = authority
+        && new_authority != vault_auth.authority().call().await?._0
+    {
+        if new_authority == Address::ZERO {
+            return Err(eyre!(
+                "authority must not be the zero address, only the owner could call the vault"
+            ));
+        }
+        ensure_has_code(&provider, new_authority, "authority").await?;
+        let action = SetAuthority::new(vault_addr, new_authority);
+        admin_actions.push(Box::new(action));
+    }
+
+    // Ownership is transferred last, since the other actions require it.
+    if let Some(new_owner) = owner
+        && new_owner != vault_auth.owner().call().await?._0
+    {
+        if new_owner == Address::ZERO {
+            return Err(eyre!("owner must not be the zero address"));
+        }
+        let multisig = cw
+            .get_product_config_value(product, network_id, "multisig_address")?
+            .parse::<Address>()?;
+        if new_owner != multisig {
+            println!(
+                "WARNING: ownership of vault {} moves from the configured multisig {} to {}",
+                vault_addr, multisig, new_owner
+            );
+        }
+        let action = TransferOwnership::new(vault_addr, new_owner);
+        admin_actions.push(Box::new(action));
+    }
+
+    Ok(())
+}
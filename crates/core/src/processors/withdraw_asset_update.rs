@@ -1,13 +1,18 @@
 use crate::{
     actions::{
         admin_action::AdminAction, set_rate_provider_data_action::SetRateProviderData,
+        set_withdraw_capacity_action::SetWithdrawCapacity,
         stop_withdraws_in_asset_action::StopWithdrawsInAsset,
         update_withdraw_asset_action::UpdateWithdrawAsset,
     },
-    bindings::{accountant::AccountantWithRateProviders, boring_queue::BoringOnChainQueue},
-    types::config_wrapper::ConfigWrapper,
+    bindings::{
+        accountant::AccountantWithRateProviders,
+        boring_queue::{BoringOnChainQueue, LegacyBoringOnChainQueue},
+    },
+    types::{config_wrapper::ConfigWrapper, contract_versions::QueueVersion},
+    utils::probe::resolve_queue_version,
 };
-use alloy::primitives::Address;
+use alloy::primitives::{Address, U256};
 use alloy::providers::ProviderBuilder;
 use eyre::{Result, eyre};
 use serde_json::Value;
@@ -71,14 +76,58 @@ pub async fn process_queue_asset_updates(
         }
     }
 
-    // Query current withdraw asset data
-    let queue = BoringOnChainQueue::new(queue_addr, provider);
-    let current_withdraw_data = queue.withdrawAssets(asset_addr).call().await?;
+    // Query current withdraw asset data, legacy queues have no withdraw capacity.
+    let version = resolve_queue_version(cw, product, network_id, &provider, queue_addr).await?;
+    let (current_withdraw_data, current_withdraw_capacity) = match version {
+        QueueVersion::V2 => {
+            let queue = BoringOnChainQueue::new(queue_addr, provider);
+            let data = queue.withdrawAssets(asset_addr).call().await?;
+            let capacity = data.withdrawCapacity;
+            (
+                LegacyBoringOnChainQueue::withdrawAssetsReturn {
+                    allowWithdraws: data.allowWithdraws,
+                    secondsToMaturity: data.secondsToMaturity,
+                    minimumSecondsToDeadline: data.minimumSecondsToDeadline,
+                    minDiscount: data.minDiscount,
+                    maxDiscount: data.maxDiscount,
+                    minimumShares: data.minimumShares,
+                },
+                Some(capacity),
+            )
+        }
+        QueueVersion::V1 => {
+            let queue = LegacyBoringOnChainQueue::new(queue_addr, provider);
+            (queue.withdrawAssets(asset_addr).call().await?, None)
+        }
+    };
+
+    // Withdraw capacities do not fit in a JSON number, so accept decimal strings too.
+    let withdraw_capacity = match asset_data.get("withdraw_capacity") {
+        Some(Value::String(s)) => Some(s.parse::<U256>()?),
+        Some(Value::Number(n)) => {
+            Some(U256::from(n.as_u64().ok_or_else(|| {
+                eyre!("withdraw_capacity must be a positive integer")
+            })?))
+        }
+        Some(_) => return Err(eyre!("withdraw_capacity must be a string or a number")),
+        None => None,
+    };
+    if withdraw_capacity.is_some() && version == QueueVersion::V1 {
+        return Err(eyre!(
+            "Legacy queue interface does not support withdraw_capacity"
+        ));
+    }
 
     // Check if withdraw settings need updating
     let allow_withdraws = asset_data["allow_withdraws"]
         .as_bool()
         .ok_or_else(|| eyre!("allow_withdraws must be a boolean"))?;
+    // Capacities are only meaningful while withdraws are allowed.
+    if withdraw_capacity.is_some() && !allow_withdraws {
+        return Err(eyre!(
+            "withdraw_capacity can not be set when allow_withdraws is false"
+        ));
+    }
     let seconds_to_maturity = asset_data["seconds_to_maturity"]
         .as_u64()
         .ok_or_else(|| eyre!("seconds_to_maturity must be a number"))?
@@ -97,6 +146,7 @@ pub async fn process_queue_asset_updates(
         .as_u64()
         .ok_or_else(|| eyre!("minimum_shares must be a number"))? as u128;
 
+    let mut updated = false;
     if allow_withdraws != current_withdraw_data.allowWithdraws
         || seconds_to_maturity != current_withdraw_data.secondsToMaturity.to::<u32>()
         || minimum_seconds_to_deadline != current_withdraw_data.minimumSecondsToDeadline.to::<u32>()
@@ -137,11 +187,61 @@ pub async fn process_queue_asset_updates(
                 minimum_shares,
             );
             admin_actions.push(Box::new(action));
+            updated = true;
         } else {
             let action = StopWithdrawsInAsset::new(queue_addr, asset_addr);
             admin_actions.push(Box::new(action));
         }
     }
 
+    // Capacity is set after the withdraw asset is updated.
+    if let Some(capacity) = capacity_to_set(withdraw_capacity, current_withdraw_capacity, updated) {
+        let action = SetWithdrawCapacity::new(queue_addr, asset_addr, capacity);
+        admin_actions.push(Box::new(action));
+    }
+
     Ok(())
 }
+
+// Capacity to set after the withdraw asset update, if any. V2 queues reset the capacity when a
+// withdraw asset is updated, so it is always set again after an update, to the requested capacity
+// or the current one. Legacy queues have no current capacity and never get one.
+fn capacity_to_set(requested: Option<U256>, current: Option<U256>, updated: bool) -> Option<U256> {
+    let current = current?;
+    match requested {
+        Some(requested) if updated || requested != current => Some(requested),
+        None if updated => Some(current),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capacity_is_set_again_after_an_update() {
+        let capacity = U256::from(100);
+        assert_eq!(
+            capacity_to_set(Some(capacity), Some(capacity), true),
+            Some(capacity)
+        );
+        assert_eq!(capacity_to_set(None, Some(capacity), true), Some(capacity));
+    }
+
+    #[test]
+    fn capacity_is_only_set_when_changed_without_an_update() {
+        let capacity = U256::from(100);
+        assert_eq!(capacity_to_set(Some(capacity), Some(capacity), false), None);
+        assert_eq!(capacity_to_set(None, Some(capacity), false), None);
+        assert_eq!(
+            capacity_to_set(Some(U256::from(50)), Some(capacity), false),
+            Some(U256::from(50))
+        );
+    }
+
+    #[test]
+    fn legacy_queues_never_set_a_capacity() {
+        assert_eq!(capacity_to_set(None, None, true), None);
+    }
+}
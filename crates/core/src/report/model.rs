@@ -0,0 +1,56 @@
+use crate::types::simulation_config::SimulationConfig;
+use eyre::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Merkle root set for the strategists of a product.
+#[derive(Clone, Debug, Serialize)]
+pub struct RootUpdate {
+    pub root: String,
+    pub strategists: Vec<String>,
+}
+
+/// Everything reported about a single Safe transaction, rendered through the report templates.
+#[derive(Clone, Debug, Serialize)]
+pub struct TransactionReport {
+    pub title: String,
+    pub network_id: u32,
+    pub multisig: String,
+    pub products: Vec<String>,
+    pub nonce: u32,
+    pub safe_hash: String,
+    /// Safe tx config, as saved to the tx file.
+    pub transaction: Value,
+    /// Descriptions of the admin actions in the tx.
+    pub actions: Vec<Value>,
+    pub root_update: Option<RootUpdate>,
+    pub proposal_url: Option<String>,
+    pub simulation_url: Option<String>,
+}
+
+impl TransactionReport {
+    pub fn new(title: &str, tx_config: &SimulationConfig, safe_hash: &str) -> Result<Self> {
+        Ok(Self {
+            title: title.to_string(),
+            network_id: tx_config.network_id,
+            multisig: tx_config.multisig.clone(),
+            products: Vec::new(),
+            nonce: tx_config.nonce,
+            safe_hash: safe_hash.to_string(),
+            transaction: serde_json::to_value(tx_config)?,
+            actions: Vec::new(),
+            root_update: None,
+            proposal_url: None,
+            simulation_url: None,
+        })
+    }
+
+    /// Sets the actions from the json descriptions of an admin batch.
+    pub fn set_actions(&mut self, descriptions: &[String]) -> Result<()> {
+        self.actions = descriptions
+            .iter()
+            .map(|description| serde_json::from_str(description))
+            .collect::<serde_json::Result<_>>()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+use eyre::{Result, eyre};
+use serde_json::Value;
+
+/// Renders a report template against a json context, using a small subset of mustache:
+/// - `{{name}}` inserts a value, with dotted paths such as `{{root_update.root}}` and array
+///   indexes such as `{{actions.0}}`. Objects and arrays are inserted as pretty printed json.
+/// - `{{#name}}...{{/name}}` renders its body once per item of a list, or once with the value as
+///   context if it is set and not empty, false or null. `{{.}}` is the current item.
+/// - `{{^name}}...{{/name}}` renders its body only if the value is missing or empty.
+///
+/// Section tags alone on their line are removed with the line. Every inserted value is passed
+/// through `escape`.
+pub fn render(template: &str, context: &Value, escape: fn(&str) -> String) -> Result<String> {
+    let template = strip_standalone_tags(template);
+    let mut out = String::new();
+    render_into(&template, &[context], escape, &mut out)?;
+    Ok(out)
+}
+
+/// Escapes text inserted into an html template.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Inserts text as is, for markdown templates.
+pub fn escape_none(text: &str) -> String {
+    text.to_string()
+}
+
+// Joins lines holding a single section tag with the next line, so sections do not leave blank
+// lines behind.
+fn strip_standalone_tags(template: &str) -> String {
+    let mut stripped = String::with_capacity(template.len());
+    for line in template.split_inclusive('\n') {
+        let tag = line.trim();
+        let standalone = tag.starts_with("{{")
+            && tag.ends_with("}}")
+            && tag.matches("{{").count() == 1
+            && matches!(tag[2..].trim_start().chars().next(), Some('#' | '^' | '/'));
+        if standalone {
+            stripped.push_str(tag);
+        } else {
+            stripped.push_str(line);
+        }
+    }
+    stripped
+}
+
+fn render_into(
+    template: &str,
+    stack: &[&Value],
+    escape: fn(&str) -> String,
+    out: &mut String,
+) -> Result<()> {
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let (tag, after) = next_tag(&rest[start..])?;
+        rest = after;
+
+        if let Some(name) = tag.strip_prefix('#').or_else(|| tag.strip_prefix('^')) {
+            let name = name.trim();
+            let (body, after) = split_section(rest, name)?;
+            rest = after;
+            let value = lookup(stack, name);
+            if tag.starts_with('^') {
+                if !is_truthy(value) {
+                    render_into(body, stack, escape, out)?;
+                }
+                continue;
+            }
+            match value {
+                Some(Value::Array(items)) => {
+                    for item in items {
+                        let mut stack = stack.to_vec();
+                        stack.push(item);
+                        render_into(body, &stack, escape, out)?;
+                    }
+                }
+                Some(value) if is_truthy(Some(value)) => {
+                    let mut stack = stack.to_vec();
+                    stack.push(value);
+                    render_into(body, &stack, escape, out)?;
+                }
+                _ => {}
+            }
+        } else if let Some(name) = tag.strip_prefix('/') {
+            return Err(eyre!(
+                "Unexpected closing tag {} in report template",
+                name.trim()
+            ));
+        } else {
+            out.push_str(&escape(&to_text(lookup(stack, tag))));
+        }
+    }
+    out.push_str(rest);
+    Ok(())
+}
+
+// Trimmed content of the tag at the start of `template`, and the template after it.
+fn next_tag(template: &str) -> Result<(&str, &str)> {
+    let end = template
+        .find("}}")
+        .ok_or_else(|| eyre!("Unclosed tag in report template"))?;
+    Ok((template[2..end].trim(), &template[end + 2..]))
+}
+
+// Body of the section `name` and the template after its closing tag, skipping over nested
+// sections with the same name.
+fn split_section<'a>(template: &'a str, name: &str) -> Result<(&'a str, &'a str)> {
+    let mut depth = 0;
+    let mut offset = 0;
+    while let Some(start) = template[offset..].find("{{") {
+        let tag_start = offset + start;
+        let (tag, after) = next_tag(&template[tag_start..])?;
+        offset = template.len() - after.len();
+        if let Some(tag_name) = tag.strip_prefix('#').or_else(|| tag.strip_prefix('^'))
+            && tag_name.trim() == name
+        {
+            depth += 1;
+        } else if let Some(tag_name) = tag.strip_prefix('/')
+            && tag_name.trim() == name
+        {
+            if depth == 0 {
+                return Ok((&template[..tag_start], after));
+            }
+            depth -= 1;
+        }
+    }
+    Err(eyre!("Section {} is never closed in report template", name))
+}
+
+// Value of a dotted path, looked up from the innermost section outwards.
+fn lookup<'a>(stack: &[&'a Value], name: &str) -> Option<&'a Value> {
+    if name == "." {
+        return stack.last().copied();
+    }
+    let mut segments = name.split('.');
+    let first = segments.next()?;
+    let mut value = stack.iter().rev().find_map(|context| context.get(first))?;
+    for segment in segments {
+        value = match segment.parse::<usize>() {
+            Ok(index) => value.get(index)?,
+            Err(_) => value.get(segment)?,
+        };
+    }
+    Some(value)
+}
+
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Object(fields)) => !fields.is_empty(),
+        Some(_) => true,
+    }
+}
+
+fn to_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(value @ (Value::Array(_) | Value::Object(_))) => {
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        }
+        Some(value) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn render_md(template: &str, context: &Value) -> Result<String> {
+        render(template, context, escape_none)
+    }
+
+    #[test]
+    fn renders_values_and_dotted_paths() {
+        let context =
+            json!({ "title": "Pause", "root_update": { "root": "0x01" }, "actions": ["a", "b"] });
+        let rendered =
+            render_md("{{ title }} {{root_update.root}} {{actions.1}}", &context).unwrap();
+        assert_eq!(rendered, "Pause 0x01 b");
+    }
+
+    #[test]
+    fn renders_missing_keys_as_empty() {
+        let context = json!({ "root_update": null });
+        let rendered = render_md(
+            "[{{missing}}][{{root_update.root}}][{{actions.3}}]",
+            &context,
+        )
+        .unwrap();
+        assert_eq!(rendered, "[][][]");
+    }
+
+    #[test]
+    fn renders_nested_sections() {
+        let context = json!({
+            "products": [
+                { "name": "a", "strategists": ["0x1", "0x2"] },
+                { "name": "b", "strategists": [] },
+            ],
+            "network_id": 1,
+        });
+        let template = concat!(
+            "{{#products}}{{name}}@{{network_id}}:",
+            "{{#strategists}}{{.}},{{/strategists}};{{/products}}"
+        );
+        assert_eq!(render_md(template, &context).unwrap(), "a@1:0x1,0x2,;b@1:;");
+    }
+
+    #[test]
+    fn renders_sections_with_the_same_name_nested() {
+        let context = json!({ "a": { "a": { "b": "inner" }, "b": "outer" } });
+        let template = "{{#a}}{{b}}-{{#a}}{{b}}{{/a}}{{/a}}";
+        assert_eq!(render_md(template, &context).unwrap(), "outer-inner");
+    }
+
+    #[test]
+    fn renders_inverted_sections() {
+        let template = "{{#url}}link {{.}}{{/url}}{{^url}}NONE{{/url}}";
+        assert_eq!(render_md(template, &json!({})).unwrap(), "NONE");
+        assert_eq!(
+            render_md(template, &json!({ "url": null })).unwrap(),
+            "NONE"
+        );
+        assert_eq!(render_md(template, &json!({ "url": "" })).unwrap(), "NONE");
+        assert_eq!(render_md(template, &json!({ "url": [] })).unwrap(), "NONE");
+        assert_eq!(
+            render_md(template, &json!({ "url": "x" })).unwrap(),
+            "link x"
+        );
+    }
+
+    #[test]
+    fn strips_standalone_section_tags() {
+        let template =
+            "- start\n{{#items}}\n- {{.}}\n{{/items}}\n{{^items}}\n- none\n{{/items}}\n- end\n";
+        let rendered = render_md(template, &json!({ "items": ["a", "b"] })).unwrap();
+        assert_eq!(rendered, "- start\n- a\n- b\n- end\n");
+        let rendered = render_md(template, &json!({})).unwrap();
+        assert_eq!(rendered, "- start\n- none\n- end\n");
+    }
+
+    #[test]
+    fn inserts_objects_as_json_and_escapes_html() {
+        let context = json!({ "action": { "to": "<a>" }, "text": "a & 'b'" });
+        let rendered = render("{{text}} {{action}}", &context, escape_html).unwrap();
+        assert_eq!(
+            rendered,
+            "a &amp; &#39;b&#39; {\n  &quot;to&quot;: &quot;&lt;a&gt;&quot;\n}"
+        );
+    }
+
+    #[test]
+    fn rejects_unclosed_tags_and_sections() {
+        let context = json!({ "items": ["a"] });
+        assert!(render_md("{{title", &context).is_err());
+        assert!(render_md("{{#items}}{{.}}", &context).is_err());
+        assert!(render_md("{{#items}}{{.}}{{/other}}", &context).is_err());
+        assert!(render_md("{{/items}}", &context).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::report::{
+    model::TransactionReport,
+    template::{escape_html, escape_none, render},
+};
+use eyre::{Result, eyre};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const MARKDOWN_TEMPLATE: &str = include_str!("templates/transaction.md");
+const HTML_TEMPLATE: &str = include_str!("templates/transaction.html");
+
+/// Output format of a transaction report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ReportFormat {
+    /// Extension of the report file, also the name of the template overriding the built in one.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            "json" => Ok(Self::Json),
+            _ => Err(eyre!(
+                "Unknown report format {}, expected markdown, html or json",
+                s
+            )),
+        }
+    }
+}
+
+/// Renders transaction reports and writes them next to the tx files. Markdown and html reports
+/// use `transaction.md` and `transaction.html` from the template directory when present, and the
+/// built in templates otherwise. Json reports are the report model itself.
+#[derive(Clone, Debug)]
+pub struct ReportWriter {
+    formats: Vec<ReportFormat>,
+    template_dir: Option<PathBuf>,
+}
+
+impl ReportWriter {
+    pub fn new(formats: Vec<ReportFormat>, template_dir: Option<PathBuf>) -> Self {
+        Self {
+            formats,
+            template_dir,
+        }
+    }
+
+    pub fn render(&self, report: &TransactionReport, format: ReportFormat) -> Result<String> {
+        let (built_in, escape): (&str, fn(&str) -> String) = match format {
+            ReportFormat::Json => return Ok(serde_json::to_string_pretty(report)?),
+            ReportFormat::Markdown => (MARKDOWN_TEMPLATE, escape_none),
+            ReportFormat::Html => (HTML_TEMPLATE, escape_html),
+        };
+
+        let template_path = self
+            .template_dir
+            .as_ref()
+            .map(|dir| dir.join(format!("transaction.{}", format.extension())))
+            .filter(|path| path.exists());
+        let template = match &template_path {
+            Some(path) => fs::read_to_string(path)
+                .map_err(|e| eyre!("Failed to read report template {}: {}", path.display(), e))?,
+            None => built_in.to_string(),
+        };
+
+        let rendered = render(&template, &serde_json::to_value(report)?, escape);
+        match &template_path {
+            Some(path) => rendered.map_err(|e| eyre!("{}: {}", path.display(), e)),
+            None => rendered,
+        }
+    }
+
+    /// Writes the report in every configured format next to the tx file, `output/single.json`
+    /// getting `output/single.report.md` and so on. Returns the paths written.
+    pub fn write(&self, report: &TransactionReport, tx_path: &str) -> Result<Vec<String>> {
+        let mut paths = Vec::with_capacity(self.formats.len());
+        for format in &self.formats {
+            let path = Path::new(tx_path).with_extension(format!("report.{}", format.extension()));
+            fs::write(&path, self.render(report, *format)?)?;
+            paths.push(path.display().to_string());
+        }
+        Ok(paths)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::bindings::accountant::{AccountantWithRateProviders, LegacyAccountantWithRateProviders};
+use crate::types::contract_versions::AccountantVersion;
+use alloy::primitives::Address;
+use alloy::providers::Provider;
+use eyre::Result;
+
+/// Accountant state fields shared by every accountant version.
+#[derive(Clone, Debug)]
+pub struct AccountantState {
+    pub payout_address: Address,
+    pub exchange_rate: u128,
+    pub allowed_exchange_rate_change_upper: u16,
+    pub allowed_exchange_rate_change_lower: u16,
+    pub last_update_timestamp: u64,
+    pub is_paused: bool,
+    pub minimum_update_delay_in_seconds: u32,
+}
+
+impl AccountantState {
+    pub async fn fetch<P: Provider>(
+        provider: P,
+        accountant: Address,
+        version: AccountantVersion,
+    ) -> Result<Self> {
+        match version {
+            AccountantVersion::V2 => {
+                let state = AccountantWithRateProviders::new(accountant, provider)
+                    .accountantState()
+                    .call()
+                    .await?;
+                Ok(Self {
+                    payout_address: state.payoutAddress,
+                    exchange_rate: state.exchangeRate.to::<u128>(),
+                    allowed_exchange_rate_change_upper: state.allowedExchangeRateChangeUpper,
+                    allowed_exchange_rate_change_lower: state.allowedExchangeRateChangeLower,
+                    last_update_timestamp: state.lastUpdateTimestamp,
+                    is_paused: state.isPaused,
+                    minimum_update_delay_in_seconds: state.minimumUpdateDelayInSeconds.to::<u32>(),
+                })
+            }
+            AccountantVersion::V1 => {
+                let state = LegacyAccountantWithRateProviders::new(accountant, provider)
+                    .accountantState()
+                    .call()
+                    .await?;
+                Ok(Self {
+                    payout_address: state.payoutAddress,
+                    exchange_rate: state.exchangeRate.to::<u128>(),
+                    allowed_exchange_rate_change_upper: state.allowedExchangeRateChangeUpper,
+                    allowed_exchange_rate_change_lower: state.allowedExchangeRateChangeLower,
+                    last_update_timestamp: state.lastUpdateTimestamp,
+                    is_paused: state.isPaused,
+                    minimum_update_delay_in_seconds: state.minimumUpdateDelayInSeconds,
+                })
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::types::simulation_config::SimulationConfig;
+use alloy::primitives::Address;
+
+/// Safe txs generated for one multisig on one network of an action file.
+///
+/// `configs` holds the Safe txs to execute in order with consecutive nonces. This is a single tx,
+/// or a propose and execute pair when a timelock is involved, unless the batch exceeded the
+/// network batch limits and was split into several parts.
+/// `descriptions[i]` describes the actions in `configs[i]`.
+pub struct AdminBatch {
+    pub network_id: u32,
+    pub multisig: Address,
+    /// Products whose actions are included, in the order they appear in the action file.
+    pub products: Vec<String>,
+    pub configs: Vec<SimulationConfig>,
+    pub descriptions: Vec<Vec<String>>,
+    /// Index of the first config executing timelocked actions, which can only be executed once
+    /// the timelock delay has passed.
+    pub execute_from: Option<usize>,
+}
+
+impl AdminBatch {
+    /// Prefix used when saving the batch txs to the output directory, identifying the network,
+    /// the multisig and the products covered.
+    pub fn label(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            self.network_id,
+            self.multisig,
+            self.products.join("+")
+        )
+    }
+
+    /// Human readable name of every config, ie `Single`, `Part 1`, `Propose` or `Execute 2`.
+    pub fn tx_names(&self) -> Vec<String> {
+        let name_group = |name: &str, count: usize| -> Vec<String> {
+            match count {
+                1 => vec![name.to_string()],
+                _ => (1..=count).map(|i| format!("{} {}", name, i)).collect(),
+            }
+        };
+
+        match self.execute_from {
+            Some(execute_from) => {
+                let mut names = name_group("Propose", execute_from);
+                names.extend(name_group("Execute", self.configs.len() - execute_from));
+                names
+            }
+            None if self.configs.len() == 1 => vec!["Single".to_string()],
+            None => name_group("Part", self.configs.len()),
+        }
+    }
+
+    /// Path every config is saved to in the output directory.
+    pub fn tx_paths(&self) -> Vec<String> {
+        self.tx_names()
+            .iter()
+            .map(|name| {
+                format!(
+                    "output/{}_{}.json",
+                    self.label(),
+                    name.to_lowercase().replace(' ', "_")
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,9 @@
+/// Per network limits a single Safe tx must stay within, anything bigger is split into several
+/// Safe txs with consecutive nonces.
+#[derive(Clone, Copy, Default)]
+pub struct BatchLimits {
+    /// Max gas a single Safe tx may use, estimated through the simulation backend.
+    pub max_gas: Option<u64>,
+    /// Max calldata size in bytes of the tx executed by the Safe.
+    pub max_calldata_bytes: Option<usize>,
+}
@@ -0,0 +1,557 @@
+use crate::types::contract_versions::ContractVersions;
+use alloy::primitives::Address;
+use eyre::{Result, eyre};
+use std::fs;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
+
+/// Edits config.toml in place, preserving its formatting and comments.
+pub struct ConfigEditor {
+    path: String,
+    document: DocumentMut,
+}
+
+impl ConfigEditor {
+    pub fn from_file(path: Option<&str>) -> Result<Self> {
+        let path = path.unwrap_or("config.toml").to_string();
+        let document = fs::read_to_string(&path)?.parse::<DocumentMut>()?;
+        Ok(Self { path, document })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, self.document.to_string())?;
+        Ok(())
+    }
+
+    // Returns the network specific table of a product, creating it after the other entries of the
+    // product if it only has a default entry, so edits never leak to other networks.
+    fn product_network_table_mut(&mut self, product: &str, network_id: u32) -> Result<&mut Table> {
+        let product_table = self
+            .document
+            .get_mut("product")
+            .and_then(|p| p.get_mut(product))
+            .and_then(|p| p.as_table_mut())
+            .ok_or_else(|| eyre!("product {} not found in config", product))?;
+
+        let key = network_id.to_string();
+        if !product_table.contains_key(&key) {
+            let mut table = Table::new();
+            table.decor_mut().set_prefix("\n");
+            if let Some(position) = product_table
+                .iter()
+                .filter_map(|(_, t)| t.as_table().and_then(|t| t.position()))
+                .max()
+            {
+                table.set_position(position + 1);
+            }
+            product_table.insert(&key, Item::Table(table));
+        }
+
+        product_table
+            .get_mut(&key)
+            .and_then(|t| t.as_table_mut())
+            .ok_or_else(|| eyre!("product.{}.{} must be a table", product, key))
+    }
+
+    /// Sets a string value in the network specific table of a product, creating the table if the
+    /// product only has a default entry so other networks are left untouched.
+    pub fn set_product_network_value(
+        &mut self,
+        product: &str,
+        network_id: u32,
+        key: &str,
+        value: &str,
+    ) -> Result<()> {
+        let table = self.product_network_table_mut(product, network_id)?;
+        set_value(table, key, Value::from(value));
+
+        Ok(())
+    }
+
+    /// Records detected interface versions as an inline `versions` table in the network specific
+    /// entry of the product, creating it if needed.
+    pub fn set_product_versions(
+        &mut self,
+        product: &str,
+        network_id: u32,
+        versions: &ContractVersions,
+    ) -> Result<()> {
+        let table = self.product_network_table_mut(product, network_id)?;
+
+        let mut inline = InlineTable::new();
+        if let Some(teller) = versions.teller {
+            inline.insert("teller", Value::from(teller.to_string()));
+        }
+        if let Some(accountant) = versions.accountant {
+            inline.insert("accountant", Value::from(accountant.to_string()));
+        }
+        if let Some(queue) = versions.queue {
+            inline.insert("queue", Value::from(queue.to_string()));
+        }
+        table.insert("versions", Item::Value(Value::InlineTable(inline)));
+
+        Ok(())
+    }
+
+    // Strategists array read for a product on a network, the network specific one if set and the
+    // default one otherwise.
+    fn product_strategists(&self, product: &str, network_id: u32) -> Option<&Array> {
+        let product_table = self.document.get("product").and_then(|p| p.get(product))?;
+        product_table
+            .get(network_id.to_string())
+            .and_then(|t| t.get("strategists"))
+            .or_else(|| {
+                product_table
+                    .get("default")
+                    .and_then(|t| t.get("strategists"))
+            })
+            .and_then(|s| s.as_array())
+    }
+
+    // Strategists array of the network specific entry of a product. If the network inherits the
+    // default strategists they are copied into its own entry first, so other networks are left
+    // untouched.
+    fn product_strategists_mut(&mut self, product: &str, network_id: u32) -> Result<&mut Array> {
+        let inherited = self
+            .product_strategists(product, network_id)
+            .cloned()
+            .unwrap_or_default();
+        let table = self.product_network_table_mut(product, network_id)?;
+        table
+            .entry("strategists")
+            .or_insert_with(|| toml_edit::value(inherited))
+            .as_array_mut()
+            .ok_or_else(|| eyre!("strategists of product {} must be an array", product))
+    }
+
+    fn is_product_strategist(&self, product: &str, network_id: u32, strategist: Address) -> bool {
+        self.product_strategists(product, network_id)
+            .is_some_and(|strategists| {
+                strategists.iter().any(|s| {
+                    s.as_str()
+                        .and_then(|s| s.parse::<Address>().ok())
+                        .is_some_and(|s| s == strategist)
+                })
+            })
+    }
+
+    /// Appends a strategist, checksummed, to the strategists of a product on a network, laid out
+    /// like the existing entries. Returns false if the strategist is already listed.
+    pub fn add_product_strategist(
+        &mut self,
+        product: &str,
+        network_id: u32,
+        strategist: &str,
+    ) -> Result<bool> {
+        let strategist = strategist
+            .parse::<Address>()
+            .map_err(|e| eyre!("strategist must be an address: {}", e))?;
+        if self.is_product_strategist(product, network_id, strategist) {
+            return Ok(false);
+        }
+
+        let strategists = self.product_strategists_mut(product, network_id)?;
+        let mut value = Value::from(strategist.to_checksum(None));
+        if let Some(last) = strategists.get_mut(strategists.len().wrapping_sub(1)) {
+            // The new entry takes the indentation of the previous one, and any whitespace before
+            // the closing bracket moves after it.
+            let prefix = last.decor().prefix().cloned();
+            let suffix = last.decor().suffix().cloned();
+            last.decor_mut().set_suffix("");
+            if let Some(prefix) = prefix {
+                value.decor_mut().set_prefix(prefix);
+            }
+            if let Some(suffix) = suffix {
+                value.decor_mut().set_suffix(suffix);
+            }
+        }
+        strategists.push_formatted(value);
+
+        Ok(true)
+    }
+
+    /// Removes a strategist from the strategists of a product on a network. Returns false if the
+    /// strategist is not listed.
+    pub fn remove_product_strategist(
+        &mut self,
+        product: &str,
+        network_id: u32,
+        strategist: &str,
+    ) -> Result<bool> {
+        let strategist = strategist
+            .parse::<Address>()
+            .map_err(|e| eyre!("strategist must be an address: {}", e))?;
+        if !self.is_product_strategist(product, network_id, strategist) {
+            return Ok(false);
+        }
+
+        let strategists = self.product_strategists_mut(product, network_id)?;
+        let index = strategists
+            .iter()
+            .position(|s| {
+                s.as_str()
+                    .and_then(|s| s.parse::<Address>().ok())
+                    .is_some_and(|s| s == strategist)
+            })
+            .ok_or_else(|| eyre!("strategist {} not found", strategist))?;
+
+        let removed = strategists.remove(index);
+        // Whitespace before the closing bracket stays there when the last entry is removed.
+        if index == strategists.len()
+            && let Some(suffix) = removed.decor().suffix().cloned()
+            && let Some(last) = strategists.get_mut(index.wrapping_sub(1))
+        {
+            last.decor_mut().set_suffix(suffix);
+        }
+
+        Ok(true)
+    }
+
+    fn products_mut(&mut self) -> Result<&mut Table> {
+        self.document
+            .get_mut("product")
+            .and_then(|p| p.as_table_mut())
+            .ok_or_else(|| eyre!("product table not found in config"))
+    }
+
+    /// Sets a value in an existing product entry, `network_id` None being the default entry.
+    /// Address values are validated and checksummed.
+    pub fn set_product_value(
+        &mut self,
+        product: &str,
+        network_id: Option<u32>,
+        key: &str,
+        value: &str,
+    ) -> Result<()> {
+        let formatted = format_product_value(key, value)?;
+        let entry = entry_key(network_id);
+        let table = self
+            .products_mut()?
+            .get_mut(product)
+            .and_then(|p| p.get_mut(&entry))
+            .and_then(|t| t.as_table_mut())
+            .ok_or_else(|| eyre!("product.{}.{} not found in config", product, entry))?;
+        set_value(table, key, formatted);
+
+        Ok(())
+    }
+
+    /// Adds a network entry to an existing product, placed after its other entries. Refuses to
+    /// overwrite an existing entry.
+    pub fn add_product_network(
+        &mut self,
+        product: &str,
+        network_id: Option<u32>,
+        values: &[(String, String)],
+    ) -> Result<()> {
+        let entry = entry_key(network_id);
+        let table = new_entry_table(values)?;
+        let product_table = self
+            .products_mut()?
+            .get_mut(product)
+            .and_then(|p| p.as_table_mut())
+            .ok_or_else(|| eyre!("product {} not found in config", product))?;
+        if product_table.contains_key(&entry) {
+            return Err(eyre!("product.{}.{} already exists", product, entry));
+        }
+
+        let mut table = table;
+        if let Some(position) = product_table
+            .iter()
+            .filter_map(|(_, t)| t.as_table().and_then(|t| t.position()))
+            .max()
+        {
+            table.set_position(position + 1);
+        }
+        product_table.insert(&entry, Item::Table(table));
+
+        Ok(())
+    }
+
+    /// Adds a new product with a single entry at the end of the config. Refuses to overwrite an
+    /// existing product.
+    pub fn add_product(
+        &mut self,
+        product: &str,
+        network_id: Option<u32>,
+        values: &[(String, String)],
+    ) -> Result<()> {
+        let table = new_entry_table(values)?;
+        let products = self.products_mut()?;
+        if products.contains_key(product) {
+            return Err(eyre!("product {} already exists", product));
+        }
+
+        let mut product_table = Table::new();
+        product_table.set_implicit(true);
+        product_table.insert(&entry_key(network_id), Item::Table(table));
+        products.insert(product, Item::Table(product_table));
+
+        Ok(())
+    }
+
+    /// Removes every entry of a product.
+    pub fn remove_product(&mut self, product: &str) -> Result<()> {
+        self.products_mut()?
+            .remove(product)
+            .ok_or_else(|| eyre!("product {} not found in config", product))?;
+
+        Ok(())
+    }
+}
+
+// Sets a value in a table, an existing value keeping its comments and position.
+fn set_value(table: &mut Table, key: &str, value: Value) {
+    match table.get_mut(key).and_then(|item| item.as_value_mut()) {
+        Some(existing) => {
+            let decor = existing.decor().clone();
+            *existing = value;
+            *existing.decor_mut() = decor;
+        }
+        None => {
+            table.insert(key, Item::Value(value));
+        }
+    }
+}
+
+fn entry_key(network_id: Option<u32>) -> String {
+    match network_id {
+        Some(network_id) => network_id.to_string(),
+        None => "default".to_string(),
+    }
+}
+
+// Product entry table holding `values`, separated from the previous table by a blank line.
+fn new_entry_table(values: &[(String, String)]) -> Result<Table> {
+    if values.is_empty() {
+        return Err(eyre!("A product entry needs at least one value"));
+    }
+
+    let mut table = Table::new();
+    table.decor_mut().set_prefix("\n");
+    for (key, value) in values {
+        if table.contains_key(key) {
+            return Err(eyre!("{} is given more than once", key));
+        }
+        table.insert(key, Item::Value(format_product_value(key, value)?));
+    }
+
+    Ok(table)
+}
+
+// Product values are strings, addresses being validated and checksummed. Strategists and versions
+// have dedicated commands.
+fn format_product_value(key: &str, value: &str) -> Result<Value> {
+    match key {
+        "strategists" | "versions" | "roles" => Err(eyre!(
+            "{} can not be set as a plain value, use its dedicated command or edit config.toml",
+            key
+        )),
+        key if key.ends_with("_address") || key == "timelock" => {
+            let address = value
+                .parse::<Address>()
+                .map_err(|e| eyre!("{} must be an address: {}", key, e))?;
+            Ok(Value::from(address.to_checksum(None)))
+        }
+        _ => Ok(Value::from(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# Product settings
+[product.liquid_eth.default]
+# Safe of the product
+multisig_address = "0x0000000000000000000000000000000000000001"
+vault_address = "0x0000000000000000000000000000000000000002"
+
+[product.liquid_eth.1]
+teller_address = "0x0000000000000000000000000000000000000003" # mainnet teller
+
+[product.other.default]
+vault_address = "0x0000000000000000000000000000000000000004"
+"#;
+
+    const CHECKSUMMED: &str = "0x52908400098527886E0F7030069857D2E4169EE7";
+
+    fn editor() -> ConfigEditor {
+        ConfigEditor {
+            path: String::new(),
+            document: CONFIG.parse().unwrap(),
+        }
+    }
+
+    fn values(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn set_product_value_keeps_comments_and_key_order() {
+        let mut editor = editor();
+        editor
+            .set_product_value(
+                "liquid_eth",
+                None,
+                "multisig_address",
+                &CHECKSUMMED.to_lowercase(),
+            )
+            .unwrap();
+        editor
+            .set_product_value("liquid_eth", Some(1), "teller_address", CHECKSUMMED)
+            .unwrap();
+        editor
+            .set_product_value("liquid_eth", Some(1), "solver_template", "v2")
+            .unwrap();
+
+        let expected = CONFIG
+            .replace("0x0000000000000000000000000000000000000001", CHECKSUMMED)
+            .replace("0x0000000000000000000000000000000000000003", CHECKSUMMED)
+            .replace(
+                "# mainnet teller\n",
+                "# mainnet teller\nsolver_template = \"v2\"\n",
+            );
+        assert_eq!(editor.document.to_string(), expected);
+    }
+
+    #[test]
+    fn set_product_value_rejects_missing_entries_and_bad_values() {
+        let mut editor = editor();
+        assert!(
+            editor
+                .set_product_value("missing", None, "teller_address", CHECKSUMMED)
+                .is_err()
+        );
+        assert!(
+            editor
+                .set_product_value("liquid_eth", Some(56), "teller_address", CHECKSUMMED)
+                .is_err()
+        );
+        assert!(
+            editor
+                .set_product_value("liquid_eth", None, "teller_address", "0x1234")
+                .is_err()
+        );
+        assert!(
+            editor
+                .set_product_value("liquid_eth", None, "strategists", CHECKSUMMED)
+                .is_err()
+        );
+        assert_eq!(editor.document.to_string(), CONFIG);
+    }
+
+    #[test]
+    fn add_product_network_goes_after_the_product_entries() {
+        let mut editor = editor();
+        editor
+            .add_product_network(
+                "liquid_eth",
+                Some(56),
+                &values(&[("teller_address", CHECKSUMMED), ("solver_template", "v2")]),
+            )
+            .unwrap();
+
+        let expected = CONFIG.replace(
+            "[product.other.default]",
+            &format!(
+                concat!(
+                    "[product.liquid_eth.56]\nteller_address = \"{}\"\n",
+                    "solver_template = \"v2\"\n\n[product.other.default]"
+                ),
+                CHECKSUMMED
+            ),
+        );
+        assert_eq!(editor.document.to_string(), expected);
+    }
+
+    #[test]
+    fn add_product_network_rejects_duplicates_and_missing_products() {
+        let mut editor = editor();
+        let entry = values(&[("teller_address", CHECKSUMMED)]);
+        assert!(
+            editor
+                .add_product_network("liquid_eth", Some(1), &entry)
+                .is_err()
+        );
+        assert!(
+            editor
+                .add_product_network("liquid_eth", None, &entry)
+                .is_err()
+        );
+        assert!(
+            editor
+                .add_product_network("missing", Some(1), &entry)
+                .is_err()
+        );
+        assert!(
+            editor
+                .add_product_network("liquid_eth", Some(56), &[])
+                .is_err()
+        );
+        assert!(
+            editor
+                .add_product_network(
+                    "liquid_eth",
+                    Some(56),
+                    &values(&[
+                        ("teller_address", CHECKSUMMED),
+                        ("teller_address", CHECKSUMMED)
+                    ])
+                )
+                .is_err()
+        );
+        assert_eq!(editor.document.to_string(), CONFIG);
+    }
+
+    #[test]
+    fn add_product_goes_at_the_end() {
+        let mut editor = editor();
+        editor
+            .add_product(
+                "new_product",
+                Some(1),
+                &values(&[("vault_address", CHECKSUMMED)]),
+            )
+            .unwrap();
+
+        let expected = format!(
+            "{}\n[product.new_product.1]\nvault_address = \"{}\"\n",
+            CONFIG, CHECKSUMMED
+        );
+        assert_eq!(editor.document.to_string(), expected);
+    }
+
+    #[test]
+    fn add_product_rejects_existing_products() {
+        let mut editor = editor();
+        assert!(
+            editor
+                .add_product("other", Some(1), &values(&[("vault_address", CHECKSUMMED)]))
+                .is_err()
+        );
+        assert_eq!(editor.document.to_string(), CONFIG);
+    }
+
+    #[test]
+    fn remove_product_removes_every_entry() {
+        let mut editor = editor();
+        editor.remove_product("other").unwrap();
+        assert_eq!(
+            editor.document.to_string(),
+            CONFIG.replace(
+                concat!(
+                    "\n[product.other.default]\n",
+                    "vault_address = \"0x0000000000000000000000000000000000000004\"\n"
+                ),
+                ""
+            )
+        );
+
+        editor.remove_product("liquid_eth").unwrap();
+        assert!(!editor.document.to_string().contains("liquid_eth"));
+        assert!(editor.remove_product("liquid_eth").is_err());
+    }
+}
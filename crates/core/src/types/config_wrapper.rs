@@ -1,25 +1,49 @@
+use crate::report::writer::{ReportFormat, ReportWriter};
+use crate::types::{
+    batch_limits::BatchLimits,
+    config_editor::ConfigEditor,
+    contract_versions::ContractVersions,
+    role_registry::{Role, RoleRegistry},
+    selector_registry::SelectorRegistry,
+    solver_template::SolverTemplate,
+};
+use alloy::primitives::Address;
 use eyre::{Result, eyre};
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 use toml::Value;
 
 pub struct ConfigWrapper {
     raw_config: Value,
+    path: Option<String>,
 }
 
 impl ConfigWrapper {
     pub fn new(raw_config: Value) -> Self {
-        Self { raw_config }
+        Self {
+            raw_config,
+            path: None,
+        }
     }
 
     pub fn from_file(path: Option<&str>) -> Result<Self> {
-        let config_content = if let Some(p) = path {
-            fs::read_to_string(p)?
-        } else {
-            fs::read_to_string("config.toml")?
-        };
+        let path = path.unwrap_or("config.toml").to_string();
+        let config_content = fs::read_to_string(&path)?;
         let raw_config: toml::Value = config_content.parse::<Value>()?;
 
-        Ok(Self { raw_config })
+        Ok(Self {
+            raw_config,
+            path: Some(path),
+        })
+    }
+
+    /// Opens the config file this wrapper was loaded from for editing, so changes can be saved
+    /// back to it.
+    pub fn editor(&self) -> Result<ConfigEditor> {
+        let path = self
+            .path
+            .as_deref()
+            .ok_or_else(|| eyre!("Config was not loaded from a file and can not be saved"))?;
+        ConfigEditor::from_file(Some(path))
     }
 
     pub fn get_product_config_value(
@@ -33,7 +57,7 @@ impl ConfigWrapper {
             .raw_config
             .get("product")
             .and_then(|p| p.get(product))
-            .and_then(|p| p.get(&network_id.to_string()))
+            .and_then(|p| p.get(network_id.to_string()))
             .and_then(|p| p.get(key))
             .or_else(|| {
                 // Fallback to default if network specific not found
@@ -69,7 +93,7 @@ impl ConfigWrapper {
             .raw_config
             .get("product")
             .and_then(|p| p.get(product))
-            .and_then(|p| p.get(&network_id.to_string()))
+            .and_then(|p| p.get(network_id.to_string()))
             .and_then(|p| p.get("strategists"))
             .or_else(|| {
                 // Fallback to default if network specific not found
@@ -96,8 +120,7 @@ impl ConfigWrapper {
             .as_str()
             .ok_or_else(|| eyre::eyre!("URL not found for network_id: {}", network_id))?;
 
-        if url_str.starts_with("env:") {
-            let env_var = &url_str[4..];
+        if let Some(env_var) = url_str.strip_prefix("env:") {
             env::var(env_var).map_err(|_| eyre::eyre!("Environment variable {} not set", env_var))
         } else {
             Ok(url_str.to_string())
@@ -121,7 +144,7 @@ impl ConfigWrapper {
         let network_value = self
             .raw_config
             .get("multi_send_address")
-            .and_then(|m| m.get(&network_id.to_string()))
+            .and_then(|m| m.get(network_id.to_string()))
             .and_then(|m| m.as_str());
 
         // Fallback to default if network specific not found
@@ -137,4 +160,416 @@ impl ConfigWrapper {
 
         Ok(address_str.to_string())
     }
+
+    pub fn get_multisend_full_address(&self, network_id: u32) -> Result<String> {
+        self.get_network_or_default_address(network_id, "multi_send_full_address")
+    }
+
+    pub fn get_sign_message_lib_address(&self, network_id: u32) -> Result<String> {
+        self.get_network_or_default_address(network_id, "sign_message_lib_address")
+    }
+
+    // Address of a top level table keyed by network id, falling back to its default entry.
+    fn get_network_or_default_address(&self, network_id: u32, key: &str) -> Result<String> {
+        let table = self.raw_config.get(key);
+        table
+            .and_then(|t| t.get(network_id.to_string()))
+            .or_else(|| table.and_then(|t| t.get("default")))
+            .and_then(|a| a.as_str())
+            .map(|a| a.to_string())
+            .ok_or_else(|| eyre::eyre!("{} not found for network_id: {}", key, network_id))
+    }
+
+    /// Names of every product in the config, in alphabetical order.
+    pub fn get_products(&self) -> Vec<String> {
+        let mut products = self
+            .raw_config
+            .get("product")
+            .and_then(|p| p.as_table())
+            .map(|p| p.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        products.sort();
+        products
+    }
+
+    /// Networks a product is deployed to, the numeric keys of its config entries in ascending
+    /// order. A `default` entry counts as mainnet unless mainnet has its own entry.
+    pub fn get_product_networks(&self, product: &str) -> Result<Vec<u32>> {
+        let entries = self
+            .raw_config
+            .get("product")
+            .and_then(|p| p.get(product))
+            .and_then(|p| p.as_table())
+            .ok_or_else(|| eyre!("product {} not found in config", product))?;
+
+        let mut networks = Vec::new();
+        for key in entries.keys() {
+            match key.as_str() {
+                "default" => networks.push(1),
+                key => networks
+                    .push(key.parse::<u32>().map_err(|_| {
+                        eyre!("Invalid network id {} for product {}", key, product)
+                    })?),
+            }
+        }
+        networks.sort_unstable();
+        networks.dedup();
+
+        Ok(networks)
+    }
+
+    /// Labels of the addresses configured for a product on a network, the config key without its
+    /// `_address` suffix, and `strategist` for strategists. Network specific values override the
+    /// default ones.
+    pub fn get_product_address_labels(
+        &self,
+        product: &str,
+        network_id: u32,
+    ) -> Vec<(Address, String)> {
+        let product_config = self.raw_config.get("product").and_then(|p| p.get(product));
+        let mut keys: Vec<String> = Vec::new();
+        for entry in ["default".to_string(), network_id.to_string()] {
+            if let Some(table) = product_config
+                .and_then(|p| p.get(&entry))
+                .and_then(|t| t.as_table())
+            {
+                keys.extend(
+                    table
+                        .keys()
+                        .filter(|k| !keys.contains(k))
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        let mut labels = Vec::new();
+        for key in keys {
+            if key == "strategists" {
+                for strategist in self
+                    .get_product_strategists(product, network_id)
+                    .unwrap_or_default()
+                {
+                    if let Ok(address) = strategist.parse::<Address>() {
+                        labels.push((address, "strategist".to_string()));
+                    }
+                }
+            } else if let Ok(address) = self
+                .get_product_config_value(product, network_id, &key)
+                .and_then(|value| Ok(value.parse::<Address>()?))
+            {
+                labels.push((address, key.trim_end_matches("_address").to_string()));
+            }
+        }
+
+        labels
+    }
+
+    /// Addresses the multisig may delegatecall into, the `default` list plus any network specific
+    /// entries.
+    pub fn get_delegate_call_allowlist(&self, network_id: u32) -> Result<Vec<Address>> {
+        self.get_address_list("delegate_call_allowlist", network_id)
+    }
+
+    /// Recipients and spenders treasury transfers and approvals may go to without a warning.
+    pub fn get_transfer_recipient_allowlist(&self, network_id: u32) -> Result<Vec<Address>> {
+        self.get_address_list("transfer_recipient_allowlist", network_id)
+    }
+
+    // Addresses listed under `default` and the network id of a top level table.
+    fn get_address_list(&self, name: &str, network_id: u32) -> Result<Vec<Address>> {
+        let list = self.raw_config.get(name);
+        let mut addresses = Vec::new();
+        for key in ["default".to_string(), network_id.to_string()] {
+            if let Some(entries) = list.and_then(|a| a.get(&key)) {
+                let entries = entries
+                    .as_array()
+                    .ok_or_else(|| eyre!("{}.{} must be an array of addresses", name, key))?;
+                for entry in entries {
+                    let address = entry
+                        .as_str()
+                        .ok_or_else(|| eyre!("{} entries must be strings", name))?
+                        .parse::<Address>()?;
+                    addresses.push(address);
+                }
+            }
+        }
+
+        Ok(addresses)
+    }
+
+    pub fn get_native_token_symbol(&self, network_id: u32) -> String {
+        let symbols = self.raw_config.get("native_token_symbol");
+        symbols
+            .and_then(|s| s.get(network_id.to_string()))
+            .or_else(|| symbols.and_then(|s| s.get("default")))
+            .and_then(|s| s.as_str())
+            .unwrap_or("ETH")
+            .to_string()
+    }
+
+    /// Batch limits for a network, network specific values override the `default` table.
+    pub fn get_batch_limits(&self, network_id: u32) -> Result<BatchLimits> {
+        let limits = self.raw_config.get("batch_limits");
+        let mut result = BatchLimits::default();
+        for key in ["default".to_string(), network_id.to_string()] {
+            let Some(table) = limits.and_then(|l| l.get(&key)) else {
+                continue;
+            };
+            if let Some(max_gas) = table.get("max_gas") {
+                let max_gas = max_gas
+                    .as_integer()
+                    .ok_or_else(|| eyre!("batch_limits.{}.max_gas must be an integer", key))?;
+                result.max_gas = Some(max_gas as u64);
+            }
+            if let Some(max_calldata_bytes) = table.get("max_calldata_bytes") {
+                let max_calldata_bytes = max_calldata_bytes.as_integer().ok_or_else(|| {
+                    eyre!("batch_limits.{}.max_calldata_bytes must be an integer", key)
+                })?;
+                result.max_calldata_bytes = Some(max_calldata_bytes as usize);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Max exchange rate change in bps a manual exchange rate update may make without an explicit
+    /// override.
+    pub fn get_exchange_rate_change_threshold_bps(&self, network_id: u32) -> Result<u64> {
+        let thresholds = self.raw_config.get("exchange_rate_change_threshold_bps");
+        let value = thresholds
+            .and_then(|t| t.get(network_id.to_string()))
+            .or_else(|| thresholds.and_then(|t| t.get("default")))
+            .ok_or_else(|| {
+                eyre!(
+                    "Exchange rate change threshold not found for network_id: {}",
+                    network_id
+                )
+            })?;
+
+        let threshold = value.as_integer().filter(|t| *t >= 0).ok_or_else(|| {
+            eyre!("exchange_rate_change_threshold_bps must be a positive integer")
+        })?;
+
+        Ok(threshold as u64)
+    }
+
+    /// Interface versions recorded in the product `versions` table. The network specific entry is
+    /// used first, and the default one only for contracts whose address also comes from the
+    /// default entry, since a network specific address may be a different deployment.
+    pub fn get_product_versions(&self, product: &str, network_id: u32) -> Result<ContractVersions> {
+        let product_config = self.raw_config.get("product").and_then(|p| p.get(product));
+        let network = product_config.and_then(|p| p.get(network_id.to_string()));
+        let default = product_config.and_then(|p| p.get("default"));
+        let version = |entry: Option<&'_ Value>, key: &str| {
+            entry
+                .and_then(|e| e.get("versions"))
+                .and_then(|v| v.get(key))
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        };
+        let lookup = |key: &str| -> Option<String> {
+            match network.and_then(|n| n.get(format!("{}_address", key))) {
+                Some(_) => version(network, key),
+                None => version(network, key).or_else(|| version(default, key)),
+            }
+        };
+
+        Ok(ContractVersions {
+            teller: lookup("teller").map(|v| v.parse()).transpose()?,
+            accountant: lookup("accountant").map(|v| v.parse()).transpose()?,
+            queue: lookup("queue").map(|v| v.parse()).transpose()?,
+        })
+    }
+
+    /// Named roles of a product on a network. The top level `[roles]` table is overridden by the
+    /// `roles` table of the product `default` entry, then by the network specific one.
+    pub fn get_role_registry(&self, product: &str, network_id: u32) -> Result<RoleRegistry> {
+        let product_config = self.raw_config.get("product").and_then(|p| p.get(product));
+        let tables = [
+            ("roles".to_string(), self.raw_config.get("roles")),
+            (
+                format!("product.{}.default.roles", product),
+                product_config
+                    .and_then(|p| p.get("default"))
+                    .and_then(|p| p.get("roles")),
+            ),
+            (
+                format!("product.{}.{}.roles", product, network_id),
+                product_config
+                    .and_then(|p| p.get(network_id.to_string()))
+                    .and_then(|p| p.get("roles")),
+            ),
+        ];
+
+        let mut registry = RoleRegistry::default();
+        for (path, table) in tables {
+            let Some(table) = table else {
+                continue;
+            };
+            let table = table
+                .as_table()
+                .ok_or_else(|| eyre!("{} must be a table of role names to ids", path))?;
+            for (name, id) in table {
+                let id = id
+                    .as_integer()
+                    .and_then(|id| u8::try_from(id).ok())
+                    .ok_or_else(|| {
+                        eyre!("{}.{} must be a role id between 0 and 255", path, name)
+                    })?;
+                registry.insert(name, id);
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Solver template `name` from the `[solver_templates]` table, with role names resolved through
+    /// the role registry of the product.
+    pub fn get_solver_template(
+        &self,
+        name: &str,
+        product: &str,
+        network_id: u32,
+    ) -> Result<SolverTemplate> {
+        let template = self
+            .raw_config
+            .get("solver_templates")
+            .and_then(|t| t.get(name))
+            .ok_or_else(|| eyre!("Solver template {} not found in config", name))?;
+        let roles = self.get_role_registry(product, network_id)?;
+        let path = format!("solver_templates.{}", name);
+
+        let resolve_role = |role: &Value| -> Result<Role> {
+            match role {
+                Value::String(role_name) => roles.by_name(role_name),
+                Value::Integer(id) => Ok(roles
+                    .by_id(u8::try_from(*id).map_err(|_| {
+                        eyre!("{} role id {} must be between 0 and 255", path, id)
+                    })?)),
+                _ => Err(eyre!("{} roles must be role names or ids", path)),
+            }
+        };
+        let list = |key: &str| -> Result<Vec<Value>> {
+            match template.get(key) {
+                Some(entries) => Ok(entries
+                    .as_array()
+                    .ok_or_else(|| eyre!("{}.{} must be an array", path, key))?
+                    .clone()),
+                None => Ok(Vec::new()),
+            }
+        };
+        let signatures = |key: &str| -> Result<Vec<String>> {
+            list(key)?
+                .iter()
+                .map(|signature| {
+                    signature
+                        .as_str()
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| eyre!("{}.{} must contain signature strings", path, key))
+                })
+                .collect()
+        };
+
+        let mut result = SolverTemplate::default();
+        for capability in list("role_capabilities")? {
+            let role = resolve_role(
+                capability
+                    .get("role")
+                    .ok_or_else(|| eyre!("{}.role_capabilities entries need a role", path))?,
+            )?;
+            let signature = capability
+                .get("signature")
+                .and_then(|s| s.as_str())
+                .ok_or_else(|| eyre!("{}.role_capabilities entries need a signature", path))?;
+            result.role_capabilities.push((role, signature.to_string()));
+        }
+        for role in list("user_roles")? {
+            result.user_roles.push(resolve_role(&role)?);
+        }
+        result.public_capabilities = signatures("public_capabilities")?;
+        result.self_solve_capabilities = signatures("self_solve_capabilities")?;
+
+        Ok(result)
+    }
+
+    /// Selector registry of the contract bindings, with the solver template signatures and the
+    /// signature files listed under `[selector_registry]` added on top.
+    pub fn get_selector_registry(&self) -> Result<SelectorRegistry> {
+        let mut registry = SelectorRegistry::from_bindings();
+
+        let templates = self
+            .raw_config
+            .get("solver_templates")
+            .and_then(|t| t.as_table())
+            .into_iter()
+            .flat_map(|t| t.values());
+        for template in templates {
+            let capabilities = template
+                .get("role_capabilities")
+                .and_then(|c| c.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|c| c.get("signature"));
+            let signatures = ["public_capabilities", "self_solve_capabilities"]
+                .iter()
+                .filter_map(|key| template.get(key).and_then(|s| s.as_array()))
+                .flatten();
+            for signature in capabilities.chain(signatures).filter_map(|s| s.as_str()) {
+                registry.insert(signature)?;
+            }
+        }
+
+        let files = self
+            .raw_config
+            .get("selector_registry")
+            .and_then(|r| r.get("signature_files"));
+        if let Some(files) = files {
+            let files = files
+                .as_array()
+                .ok_or_else(|| eyre!("selector_registry.signature_files must be an array"))?;
+            for file in files {
+                let file = file.as_str().ok_or_else(|| {
+                    eyre!("selector_registry.signature_files must contain file paths")
+                })?;
+                registry.load_file(file)?;
+            }
+        }
+
+        Ok(registry)
+    }
+
+    /// Report writer for the formats and template directory of the `[reports]` table, writing
+    /// markdown, html and json reports with the built in templates if it is missing.
+    pub fn get_report_writer(&self) -> Result<ReportWriter> {
+        let reports = self.raw_config.get("reports");
+        let formats = match reports.and_then(|r| r.get("formats")) {
+            Some(formats) => formats
+                .as_array()
+                .ok_or_else(|| eyre!("reports.formats must be an array"))?
+                .iter()
+                .map(|format| {
+                    format
+                        .as_str()
+                        .ok_or_else(|| eyre!("reports.formats must contain format names"))?
+                        .parse::<ReportFormat>()
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![
+                ReportFormat::Markdown,
+                ReportFormat::Html,
+                ReportFormat::Json,
+            ],
+        };
+        let template_dir = reports
+            .and_then(|r| r.get("template_dir"))
+            .map(|dir| {
+                dir.as_str()
+                    .map(PathBuf::from)
+                    .ok_or_else(|| eyre!("reports.template_dir must be a path"))
+            })
+            .transpose()?;
+
+        Ok(ReportWriter::new(formats, template_dir))
+    }
 }
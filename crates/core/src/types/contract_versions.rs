@@ -0,0 +1,73 @@
+use eyre::{Result, eyre};
+use std::fmt;
+use std::str::FromStr;
+
+/// Teller interface generation.
+///
+/// `V1` is the legacy teller that only supports `addAsset`/`removeAsset`, `V2` supports
+/// `updateAssetData` and the `assetData` getter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TellerVersion {
+    V1,
+    V2,
+}
+
+/// Accountant interface generation.
+///
+/// `V1` is the legacy accountant with a single management fee set through `updateManagementFee`,
+/// `V2` has separate platform and performance fees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountantVersion {
+    V1,
+    V2,
+}
+
+/// BoringOnChainQueue interface generation.
+///
+/// `V2` adds a withdraw capacity to every withdraw asset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueueVersion {
+    V1,
+    V2,
+}
+
+/// Interface versions of a product's contracts on a network, `None` if not known.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ContractVersions {
+    pub teller: Option<TellerVersion>,
+    pub accountant: Option<AccountantVersion>,
+    pub queue: Option<QueueVersion>,
+}
+
+macro_rules! impl_version_str {
+    ($name:ident) => {
+        impl FromStr for $name {
+            type Err = eyre::Report;
+
+            fn from_str(s: &str) -> Result<Self> {
+                match s.to_lowercase().as_str() {
+                    "v1" => Ok(Self::V1),
+                    "v2" => Ok(Self::V2),
+                    _ => Err(eyre!(
+                        "Unknown {} version: {}, must be v1 or v2",
+                        stringify!($name),
+                        s
+                    )),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Self::V1 => write!(f, "v1"),
+                    Self::V2 => write!(f, "v2"),
+                }
+            }
+        }
+    };
+}
+
+impl_version_str!(TellerVersion);
+impl_version_str!(AccountantVersion);
+impl_version_str!(QueueVersion);
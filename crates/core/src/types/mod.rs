@@ -1,3 +1,12 @@
+pub mod accountant_state;
+pub mod admin_batch;
+pub mod batch_limits;
+pub mod config_editor;
 pub mod config_wrapper;
+pub mod contract_versions;
+pub mod permission_matrix;
+pub mod role_registry;
+pub mod selector_registry;
 pub mod simulation_config;
+pub mod solver_template;
 pub mod transaction;
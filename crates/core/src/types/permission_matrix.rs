@@ -0,0 +1,396 @@
+use crate::{
+    bindings::{auth::Auth, roles_authority::RolesAuthority},
+    types::{
+        role_registry::RoleRegistry,
+        selector_registry::{SelectorRegistry, UNKNOWN_SELECTOR},
+    },
+    utils::discover::get_event_logs,
+};
+use alloy::primitives::{Address, FixedBytes};
+use alloy::providers::Provider;
+use alloy::sol_types::SolEvent;
+use eyre::{Result, eyre};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Output format of a permission matrix export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PermissionFormat {
+    Json,
+    Csv,
+    Markdown,
+    Dot,
+}
+
+impl FromStr for PermissionFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "dot" | "graphviz" => Ok(Self::Dot),
+            _ => Err(eyre!(
+                "Unknown format {}, expected json, csv, markdown or dot",
+                s
+            )),
+        }
+    }
+}
+
+/// Names used when exporting a permission matrix, unlabeled addresses are shown as is and
+/// selectors missing from the registry are flagged as unknown.
+#[derive(Clone, Debug, Default)]
+pub struct PermissionLabels {
+    pub addresses: HashMap<Address, String>,
+    pub roles: RoleRegistry,
+    pub selectors: SelectorRegistry,
+}
+
+impl PermissionLabels {
+    fn address(&self, address: &Address) -> String {
+        self.addresses.get(address).cloned().unwrap_or_default()
+    }
+
+    fn selector(&self, selector: &FixedBytes<4>) -> String {
+        self.selectors.label(selector)
+    }
+
+    fn role(&self, role: u8) -> String {
+        self.roles.by_id(role).name.unwrap_or_default()
+    }
+}
+
+/// Current state of a RolesAuthority, folded from its events in order.
+#[derive(Clone, Debug, Default)]
+pub struct PermissionMatrix {
+    pub roles_authority: Address,
+    pub owner: Address,
+    pub authority: Address,
+    /// Users holding each role.
+    pub members: BTreeMap<u8, BTreeSet<Address>>,
+    /// Roles allowed to call each target and selector.
+    pub capabilities: BTreeMap<(Address, FixedBytes<4>), BTreeSet<u8>>,
+    /// Target and selectors anyone may call.
+    pub public_capabilities: BTreeSet<(Address, FixedBytes<4>)>,
+}
+
+impl PermissionMatrix {
+    /// Scans the RolesAuthority events emitted since `from_block`. The owner and authority are
+    /// read on chain if no event since `from_block` sets them.
+    pub async fn fetch<P: Provider + Clone>(
+        provider: P,
+        roles_authority: Address,
+        from_block: u64,
+    ) -> Result<Self> {
+        let logs = get_event_logs(
+            &provider,
+            roles_authority,
+            vec![
+                RolesAuthority::UserRoleUpdated::SIGNATURE_HASH,
+                RolesAuthority::RoleCapabilityUpdated::SIGNATURE_HASH,
+                RolesAuthority::PublicCapabilityUpdated::SIGNATURE_HASH,
+                RolesAuthority::OwnershipTransferred::SIGNATURE_HASH,
+                RolesAuthority::AuthorityUpdated::SIGNATURE_HASH,
+            ],
+            from_block,
+        )
+        .await?;
+
+        let mut matrix = PermissionMatrix {
+            roles_authority,
+            ..Default::default()
+        };
+        for log in logs {
+            if let Ok(event) = log.log_decode::<RolesAuthority::UserRoleUpdated>() {
+                let event = event.inner.data;
+                let members = matrix.members.entry(event.role).or_default();
+                if event.enabled {
+                    members.insert(event.user);
+                } else {
+                    members.remove(&event.user);
+                }
+            } else if let Ok(event) = log.log_decode::<RolesAuthority::RoleCapabilityUpdated>() {
+                let event = event.inner.data;
+                let roles = matrix
+                    .capabilities
+                    .entry((event.target, event.functionSig))
+                    .or_default();
+                if event.enabled {
+                    roles.insert(event.role);
+                } else {
+                    roles.remove(&event.role);
+                }
+            } else if let Ok(event) = log.log_decode::<RolesAuthority::PublicCapabilityUpdated>() {
+                let event = event.inner.data;
+                if event.enabled {
+                    matrix
+                        .public_capabilities
+                        .insert((event.target, event.functionSig));
+                } else {
+                    matrix
+                        .public_capabilities
+                        .remove(&(event.target, event.functionSig));
+                }
+            } else if let Ok(event) = log.log_decode::<RolesAuthority::OwnershipTransferred>() {
+                matrix.owner = event.inner.data.newOwner;
+            } else if let Ok(event) = log.log_decode::<RolesAuthority::AuthorityUpdated>() {
+                matrix.authority = event.inner.data.newAuthority;
+            }
+        }
+        matrix.members.retain(|_, members| !members.is_empty());
+        matrix.capabilities.retain(|_, roles| !roles.is_empty());
+
+        let auth = Auth::new(roles_authority, provider);
+        if matrix.owner == Address::ZERO {
+            matrix.owner = auth.owner().call().await?._0;
+        }
+        if matrix.authority == Address::ZERO {
+            matrix.authority = auth.authority().call().await?._0;
+        }
+
+        Ok(matrix)
+    }
+
+    /// Renders the matrix in the given format.
+    pub fn export(&self, format: PermissionFormat, labels: &PermissionLabels) -> Result<String> {
+        match format {
+            PermissionFormat::Json => Ok(serde_json::to_string_pretty(&self.to_json(labels))?),
+            PermissionFormat::Csv => Ok(self.to_csv(labels)),
+            PermissionFormat::Markdown => Ok(self.to_markdown(labels)),
+            PermissionFormat::Dot => Ok(self.to_dot(labels)),
+        }
+    }
+
+    // Every role that has members or capabilities.
+    fn roles(&self) -> BTreeSet<u8> {
+        self.members
+            .keys()
+            .copied()
+            .chain(self.capabilities.values().flatten().copied())
+            .collect()
+    }
+
+    // Target and selector of every capability of a role.
+    fn role_capabilities(&self, role: u8) -> Vec<(Address, FixedBytes<4>)> {
+        self.capabilities
+            .iter()
+            .filter(|(_, roles)| roles.contains(&role))
+            .map(|(capability, _)| *capability)
+            .collect()
+    }
+
+    fn to_json(&self, labels: &PermissionLabels) -> Value {
+        let capability = |(target, selector): (Address, FixedBytes<4>)| {
+            json!({
+                "target": target.to_string(),
+                "target_label": labels.address(&target),
+                "selector": selector.to_string(),
+                "signature": labels.selector(&selector),
+            })
+        };
+        let roles = self
+            .roles()
+            .into_iter()
+            .map(|role| {
+                let members = self
+                    .members
+                    .get(&role)
+                    .into_iter()
+                    .flatten()
+                    .map(|member| {
+                        json!({
+                            "address": member.to_string(),
+                            "label": labels.address(member),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "id": role,
+                    "name": labels.role(role),
+                    "members": members,
+                    "capabilities": self
+                        .role_capabilities(role)
+                        .into_iter()
+                        .map(capability)
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "roles_authority": self.roles_authority.to_string(),
+            "owner": self.owner.to_string(),
+            "owner_label": labels.address(&self.owner),
+            "authority": self.authority.to_string(),
+            "roles": roles,
+            "public_capabilities": self
+                .public_capabilities
+                .iter()
+                .copied()
+                .map(capability)
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    fn to_csv(&self, labels: &PermissionLabels) -> String {
+        let mut csv =
+            String::from("kind,role_id,role_name,address,address_label,selector,signature\n");
+        for (role, members) in &self.members {
+            for member in members {
+                let _ = writeln!(
+                    csv,
+                    "member,{},{},{},{},,",
+                    role,
+                    labels.role(*role),
+                    member,
+                    csv_field(&labels.address(member))
+                );
+            }
+        }
+        for ((target, selector), roles) in &self.capabilities {
+            for role in roles {
+                let _ = writeln!(
+                    csv,
+                    "capability,{},{},{},{},{},{}",
+                    role,
+                    labels.role(*role),
+                    target,
+                    csv_field(&labels.address(target)),
+                    selector,
+                    csv_field(&labels.selector(selector))
+                );
+            }
+        }
+        for (target, selector) in &self.public_capabilities {
+            let _ = writeln!(
+                csv,
+                "public,,,{},{},{},{}",
+                target,
+                csv_field(&labels.address(target)),
+                selector,
+                csv_field(&labels.selector(selector))
+            );
+        }
+        csv
+    }
+
+    fn to_markdown(&self, labels: &PermissionLabels) -> String {
+        let label = |address: &Address| match labels.address(address) {
+            label if label.is_empty() => format!("`{}`", address),
+            label => format!("`{}` ({})", address, label),
+        };
+        let signature = |selector: &FixedBytes<4>| match labels.selector(selector) {
+            signature if signature == UNKNOWN_SELECTOR => {
+                format!("`{}` **{}**", selector, signature)
+            }
+            signature => format!("`{}` `{}`", selector, signature),
+        };
+
+        let mut md = String::new();
+        let _ = writeln!(md, "# Roles Authority {}\n", self.roles_authority);
+        let _ = writeln!(md, "- Owner: {}", label(&self.owner));
+        let _ = writeln!(md, "- Authority: {}", label(&self.authority));
+
+        for role in self.roles() {
+            let _ = writeln!(md, "\n## Role {}\n", labels.roles.by_id(role));
+            let _ = writeln!(md, "### Members\n");
+            for member in self.members.get(&role).into_iter().flatten() {
+                let _ = writeln!(md, "- {}", label(member));
+            }
+            let _ = writeln!(md, "\n### Capabilities\n");
+            let _ = writeln!(md, "| Target | Function |");
+            let _ = writeln!(md, "| --- | --- |");
+            for (target, selector) in self.role_capabilities(role) {
+                let _ = writeln!(md, "| {} | {} |", label(&target), signature(&selector));
+            }
+        }
+
+        let _ = writeln!(md, "\n## Public Capabilities\n");
+        let _ = writeln!(md, "| Target | Function |");
+        let _ = writeln!(md, "| --- | --- |");
+        for (target, selector) in &self.public_capabilities {
+            let _ = writeln!(md, "| {} | {} |", label(target), signature(selector));
+        }
+        md
+    }
+
+    fn to_dot(&self, labels: &PermissionLabels) -> String {
+        let node_label = |address: &Address| match labels.address(address) {
+            label if label.is_empty() => address.to_string(),
+            label => format!("{}\\n{}", label, address),
+        };
+        let function_label = |target: &Address, selector: &FixedBytes<4>| {
+            let target = match labels.address(target) {
+                label if label.is_empty() => target.to_string(),
+                label => label,
+            };
+            match labels.selector(selector) {
+                signature if signature == UNKNOWN_SELECTOR => {
+                    format!("{}\\n{} ({})", target, selector, signature)
+                }
+                signature => format!("{}\\n{}", target, signature),
+            }
+        };
+
+        let mut dot = String::from("digraph permissions {\n    rankdir=LR;\n");
+        for role in self.roles() {
+            let _ = writeln!(
+                dot,
+                "    \"role_{}\" [label=\"{}\", shape=ellipse];",
+                role,
+                labels.roles.by_id(role)
+            );
+        }
+        for (role, members) in &self.members {
+            for member in members {
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" [label=\"{}\", shape=box];",
+                    member,
+                    node_label(member)
+                );
+                let _ = writeln!(dot, "    \"{}\" -> \"role_{}\";", member, role);
+            }
+        }
+        for ((target, selector), roles) in &self.capabilities {
+            let _ = writeln!(
+                dot,
+                "    \"{}_{}\" [label=\"{}\", shape=note];",
+                target,
+                selector,
+                function_label(target, selector)
+            );
+            for role in roles {
+                let _ = writeln!(dot, "    \"role_{}\" -> \"{}_{}\";", role, target, selector);
+            }
+        }
+        if !self.public_capabilities.is_empty() {
+            dot.push_str("    \"public\" [label=\"PUBLIC\", shape=ellipse];\n");
+        }
+        for (target, selector) in &self.public_capabilities {
+            let _ = writeln!(
+                dot,
+                "    \"{}_{}\" [label=\"{}\", shape=note];",
+                target,
+                selector,
+                function_label(target, selector)
+            );
+            let _ = writeln!(dot, "    \"public\" -> \"{}_{}\";", target, selector);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Quotes CSV fields containing separators, which function signatures do.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
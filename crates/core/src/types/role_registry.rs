@@ -0,0 +1,83 @@
+use eyre::{Result, eyre};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A RolesAuthority role id, with its name if the role registry has one for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Role {
+    pub id: u8,
+    pub name: Option<String>,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{} ({})", name, self.id),
+            None => write!(f, "{}", self.id),
+        }
+    }
+}
+
+/// Role names of a product on a network, built from the `[roles]` table of the config with the
+/// product `roles` tables layered on top.
+#[derive(Clone, Debug, Default)]
+pub struct RoleRegistry {
+    roles: BTreeMap<String, u8>,
+}
+
+impl RoleRegistry {
+    /// Adds or overrides a named role.
+    pub fn insert(&mut self, name: &str, id: u8) {
+        self.roles.insert(name.to_string(), id);
+    }
+
+    /// Role with the given name, erroring if the registry does not have it.
+    pub fn by_name(&self, name: &str) -> Result<Role> {
+        let id = self
+            .roles
+            .get(name)
+            .ok_or_else(|| eyre!("Role {} not found in the roles registry", name))?;
+        Ok(self.by_id(*id))
+    }
+
+    /// Role with the given id, named if the registry has a name for it. When several names map to
+    /// the same id, the first in alphabetical order is used.
+    pub fn by_id(&self, id: u8) -> Role {
+        let name = self
+            .roles
+            .iter()
+            .find(|(_, role_id)| **role_id == id)
+            .map(|(name, _)| name.clone());
+        Role { id, name }
+    }
+
+    /// Role given in an action file, either as a role name or as a number.
+    pub fn resolve(&self, value: &Value) -> Result<Role> {
+        match value {
+            Value::String(name) => self.by_name(name),
+            Value::Number(id) => {
+                let id = id
+                    .as_u64()
+                    .and_then(|id| u8::try_from(id).ok())
+                    .ok_or_else(|| eyre!("Role id {} must be between 0 and 255", id))?;
+                Ok(self.by_id(id))
+            }
+            _ => Err(eyre!("Role must be a role name or a number")),
+        }
+    }
+
+    /// Every named role, ordered by id.
+    pub fn roles(&self) -> Vec<Role> {
+        let mut roles = self
+            .roles
+            .iter()
+            .map(|(name, id)| Role {
+                id: *id,
+                name: Some(name.clone()),
+            })
+            .collect::<Vec<_>>();
+        roles.sort_by_key(|role| role.id);
+        roles
+    }
+}
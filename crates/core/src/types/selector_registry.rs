@@ -0,0 +1,181 @@
+use crate::bindings::{
+    accountant::{AccountantWithRateProviders, LegacyAccountantWithRateProviders},
+    auth::Auth,
+    boring_queue::{BoringOnChainQueue, LegacyBoringOnChainQueue},
+    boring_vault::BoringVault,
+    erc20::ERC20,
+    guard::Guard,
+    manager::ManagerWithMerkleVerification,
+    multisend::{MultiSend, MutliSendCallOnly},
+    multisig::GnosisSafe,
+    roles_authority::RolesAuthority,
+    sign_message_lib::SignMessageLib,
+    teller::TellerWithMultiAssetSupport,
+    timelock::Timelock,
+};
+use alloy::dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy::json_abi::Function;
+use alloy::primitives::FixedBytes;
+use eyre::{Result, eyre};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::fs;
+
+/// Label used in reports for selectors the registry has no signature for.
+pub const UNKNOWN_SELECTOR: &str = "UNKNOWN";
+
+/// Function signatures by selector, built from the contract bindings with signatures from the
+/// config and user supplied signature files added on top.
+#[derive(Clone, Debug, Default)]
+pub struct SelectorRegistry {
+    functions: HashMap<FixedBytes<4>, Function>,
+}
+
+impl SelectorRegistry {
+    /// Registry of every function in the contract bindings.
+    pub fn from_bindings() -> Self {
+        let mut registry = Self::default();
+        for functions in [
+            AccountantWithRateProviders::abi::functions(),
+            LegacyAccountantWithRateProviders::abi::functions(),
+            Auth::abi::functions(),
+            BoringOnChainQueue::abi::functions(),
+            LegacyBoringOnChainQueue::abi::functions(),
+            BoringVault::abi::functions(),
+            ERC20::abi::functions(),
+            Guard::abi::functions(),
+            ManagerWithMerkleVerification::abi::functions(),
+            MultiSend::abi::functions(),
+            MutliSendCallOnly::abi::functions(),
+            GnosisSafe::abi::functions(),
+            RolesAuthority::abi::functions(),
+            SignMessageLib::abi::functions(),
+            TellerWithMultiAssetSupport::abi::functions(),
+            Timelock::abi::functions(),
+        ] {
+            for function in functions.into_values().flatten() {
+                registry.insert_function(function);
+            }
+        }
+        registry
+    }
+
+    // The first signature registered for a selector is kept, so bindings win over collisions in
+    // user supplied signatures.
+    fn insert_function(&mut self, function: Function) {
+        self.functions
+            .entry(function.selector())
+            .or_insert(function);
+    }
+
+    /// Adds a human readable function signature such as `transfer(address,uint256)`.
+    pub fn insert(&mut self, signature: &str) -> Result<()> {
+        let function = Function::parse(signature)
+            .map_err(|e| eyre!("Invalid function signature {}: {}", signature, e))?;
+        self.insert_function(function);
+        Ok(())
+    }
+
+    /// Adds every signature of a file with one signature per line. Empty lines and lines starting
+    /// with `#` are skipped.
+    pub fn load_file(&mut self, path: &str) -> Result<()> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read signature file {}: {}", path, e))?;
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            self.insert(line)
+                .map_err(|e| eyre!("{} in signature file {}", e, path))?;
+        }
+        Ok(())
+    }
+
+    /// Signature of a selector, if the registry has one.
+    pub fn signature(&self, selector: &FixedBytes<4>) -> Option<String> {
+        self.functions
+            .get(selector)
+            .map(|function| function.signature())
+    }
+
+    /// Signature of a selector, or `UNKNOWN` if the registry does not have it.
+    pub fn label(&self, selector: &FixedBytes<4>) -> String {
+        self.signature(selector)
+            .unwrap_or_else(|| UNKNOWN_SELECTOR.to_string())
+    }
+
+    /// Signature of the function called by some calldata, `UNKNOWN` for unknown selectors and
+    /// `None` for calldata too short to hold a selector, such as plain native transfers.
+    pub fn label_calldata(&self, data: &[u8]) -> Option<String> {
+        (data.len() >= 4).then(|| self.label(&FixedBytes::<4>::from_slice(&data[..4])))
+    }
+
+    /// Decodes calldata into its selector, signature and arguments. Arguments are left out when
+    /// the selector is unknown or the calldata does not match the registered signature.
+    pub fn decode_calldata(&self, data: &[u8]) -> Result<Value> {
+        if data.len() < 4 {
+            return Err(eyre!("Calldata must be at least 4 bytes long"));
+        }
+        let selector = FixedBytes::<4>::from_slice(&data[..4]);
+        let Some(function) = self.functions.get(&selector) else {
+            return Ok(json!({
+                "selector": selector.to_string(),
+                "signature": UNKNOWN_SELECTOR,
+            }));
+        };
+
+        let mut decoded = json!({
+            "selector": selector.to_string(),
+            "signature": function.signature(),
+        });
+        match function.abi_decode_input(&data[4..], true) {
+            Ok(values) => {
+                let args = function
+                    .inputs
+                    .iter()
+                    .zip(&values)
+                    .map(|(param, value)| (param.name.clone(), value_to_string(value)))
+                    .collect::<Vec<_>>();
+                // Binding functions have named parameters, signature file ones do not. Named
+                // arguments are kept in parameter order.
+                decoded["args"] = if args.iter().all(|(name, _)| !name.is_empty()) {
+                    json!(
+                        args.into_iter()
+                            .map(|(name, value)| json!({ "name": name, "value": value }))
+                            .collect::<Vec<_>>()
+                    )
+                } else {
+                    json!(args.into_iter().map(|(_, value)| value).collect::<Vec<_>>())
+                };
+            }
+            Err(e) => decoded["error"] = json!(format!("Failed to decode arguments: {}", e)),
+        }
+        Ok(decoded)
+    }
+}
+
+// Formats a decoded value the way raw call arguments are written, arrays and tuples as `[a, b]`
+// and `(a, b)`.
+fn value_to_string(value: &DynSolValue) -> String {
+    let join = |values: &[DynSolValue]| {
+        values
+            .iter()
+            .map(value_to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match value {
+        DynSolValue::Bool(b) => b.to_string(),
+        DynSolValue::Int(i, _) => i.to_string(),
+        DynSolValue::Uint(u, _) => u.to_string(),
+        DynSolValue::FixedBytes(word, size) => format!("0x{}", hex::encode(&word[..*size])),
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Function(function) => function.to_string(),
+        DynSolValue::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        DynSolValue::String(s) => s.clone(),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", join(values))
+        }
+        DynSolValue::Tuple(values) => format!("({})", join(values)),
+    }
+}
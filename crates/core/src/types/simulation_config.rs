@@ -30,10 +30,10 @@ impl SimulationConfig {
     }
 
     pub fn value(&self) -> U256 {
-        U256::from(self.value.parse::<U256>().expect("Failed to parse value"))
+        self.value.parse::<U256>().expect("Failed to parse value")
     }
 
     pub fn data(&self) -> Bytes {
-        Bytes::from(self.data.parse::<Bytes>().expect("Failed to parse data"))
+        self.data.parse::<Bytes>().expect("Failed to parse data")
     }
 }
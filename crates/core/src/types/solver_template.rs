@@ -0,0 +1,15 @@
+use crate::types::role_registry::Role;
+
+/// RolesAuthority permissions a solver generation needs, applied to the solver contract by
+/// `update_solver`. A setup grants them in order, a tear down revokes them in reverse order.
+#[derive(Clone, Debug, Default)]
+pub struct SolverTemplate {
+    /// Roles allowed to call each function signature of the solver.
+    pub role_capabilities: Vec<(Role, String)>,
+    /// Roles granted to the solver contract itself.
+    pub user_roles: Vec<Role>,
+    /// Function signatures of the solver anyone may call.
+    pub public_capabilities: Vec<String>,
+    /// Function signatures made public only when self solves are allowed.
+    pub self_solve_capabilities: Vec<String>,
+}
@@ -1,8 +1,22 @@
 use alloy::primitives::{Address, Bytes, U256};
 
+/// Safe operation type, encoded as a single byte in Safe txs and MultiSend payloads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Call = 0,
+    DelegateCall = 1,
+}
+
+impl Operation {
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
 #[derive(Clone)]
 pub struct Transaction {
     pub to: Address,
     pub value: U256,
     pub data: Bytes,
+    pub operation: Operation,
 }
@@ -0,0 +1,316 @@
+use crate::{
+    bindings::{
+        accountant::AccountantWithRateProviders, auth::Auth, boring_queue::BoringOnChainQueue,
+        boring_vault::BoringVault, manager::ManagerWithMerkleVerification,
+        roles_authority::RolesAuthority, teller::TellerWithMultiAssetSupport, timelock::Timelock,
+    },
+    types::config_wrapper::ConfigWrapper,
+    utils::probe::code_has_function,
+};
+use alloy::primitives::{Address, B256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::{Filter, Log};
+use alloy::sol_types::SolEvent;
+use eyre::{Result, eyre};
+
+/// Product contracts found on chain from a vault address, `None` for the ones that were not found.
+#[derive(Debug, Default)]
+pub struct DiscoveredProduct {
+    pub multisig: Option<Address>,
+    pub vault: Address,
+    pub manager: Option<Address>,
+    pub accountant: Option<Address>,
+    pub teller: Option<Address>,
+    pub queue: Option<Address>,
+    pub roles_authority: Option<Address>,
+    pub timelock: Option<Address>,
+}
+
+impl DiscoveredProduct {
+    /// Config keys and checksummed addresses of every contract found, in config order.
+    pub fn config_values(&self) -> Vec<(String, String)> {
+        [
+            ("multisig_address", self.multisig),
+            ("vault_address", Some(self.vault)),
+            ("manager_address", self.manager),
+            ("accountant_address", self.accountant),
+            ("teller_address", self.teller),
+            ("queue_address", self.queue),
+            ("roles_authority_address", self.roles_authority),
+            ("timelock_address", self.timelock),
+        ]
+        .into_iter()
+        .filter_map(|(key, address)| address.map(|a| (key.to_string(), a.to_checksum(None))))
+        .collect()
+    }
+
+    /// Names of the contracts every product has that were not found. The timelock is optional.
+    pub fn missing(&self) -> Vec<&'static str> {
+        [
+            ("multisig", self.multisig),
+            ("manager", self.manager),
+            ("accountant", self.accountant),
+            ("teller", self.teller),
+            ("queue", self.queue),
+            ("roles authority", self.roles_authority),
+        ]
+        .into_iter()
+        .filter_map(|(name, address)| address.is_none().then_some(name))
+        .collect()
+    }
+}
+
+// Kind of product contract, told apart by the functions in its bytecode.
+#[derive(Clone, Copy, PartialEq)]
+enum ContractKind {
+    Manager,
+    Teller,
+    Accountant,
+    Queue,
+}
+
+fn classify(code: &[u8]) -> Option<ContractKind> {
+    if code_has_function(code, "manageRoot(address)") {
+        Some(ContractKind::Manager)
+    } else if code_has_function(code, "shareLockPeriod()") {
+        Some(ContractKind::Teller)
+    } else if code_has_function(code, "getRate()") && code_has_function(code, "accountantState()") {
+        Some(ContractKind::Accountant)
+    } else if code_has_function(code, "getRequestIds()") {
+        Some(ContractKind::Queue)
+    } else {
+        None
+    }
+}
+
+// Largest block range fetched in a single log query.
+const MAX_LOG_RANGE: u64 = 100_000;
+
+/// Logs emitted by `address` from `from_block` to the latest block for the given event
+/// signatures. Logs are fetched in bounded block ranges, since RPCs reject or cap queries over
+/// large ranges or with too many results. A failing range is halved and retried, down to a single
+/// block, and the range grows back after every successful query.
+pub async fn get_event_logs<P: Provider>(
+    provider: &P,
+    address: Address,
+    signatures: Vec<B256>,
+    from_block: u64,
+) -> Result<Vec<Log>> {
+    let latest = provider.get_block_number().await?;
+    let mut logs = Vec::new();
+    let mut start = from_block;
+    let mut range = MAX_LOG_RANGE;
+    while start <= latest {
+        let end = latest.min(start + range - 1);
+        let filter = Filter::new()
+            .address(address)
+            .event_signature(signatures.clone())
+            .from_block(start)
+            .to_block(end);
+        match provider.get_logs(&filter).await {
+            Ok(page) => {
+                logs.extend(page);
+                start = end + 1;
+                range = (range * 2).min(MAX_LOG_RANGE);
+            }
+            Err(_) if range > 1 => range /= 2,
+            Err(e) => {
+                return Err(eyre!(
+                    "Failed to fetch logs of {} in block {}: {}",
+                    address,
+                    start,
+                    e
+                ));
+            }
+        }
+    }
+    Ok(logs)
+}
+
+// Every user granted a role and every target given a capability in the roles authority.
+async fn roles_authority_addresses<P: Provider>(
+    provider: &P,
+    roles_authority: Address,
+    from_block: u64,
+) -> Result<Vec<Address>> {
+    let logs = get_event_logs(
+        provider,
+        roles_authority,
+        vec![
+            RolesAuthority::UserRoleUpdated::SIGNATURE_HASH,
+            RolesAuthority::RoleCapabilityUpdated::SIGNATURE_HASH,
+            RolesAuthority::PublicCapabilityUpdated::SIGNATURE_HASH,
+        ],
+        from_block,
+    )
+    .await?;
+
+    let mut addresses = Vec::new();
+    for log in logs {
+        let address = if let Ok(event) = log.log_decode::<RolesAuthority::UserRoleUpdated>() {
+            event.inner.data.user
+        } else if let Ok(event) = log.log_decode::<RolesAuthority::RoleCapabilityUpdated>() {
+            event.inner.data.target
+        } else if let Ok(event) = log.log_decode::<RolesAuthority::PublicCapabilityUpdated>() {
+            event.inner.data.target
+        } else {
+            continue;
+        };
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    Ok(addresses)
+}
+
+// Vault a product contract points back at, None if the getter is missing or reverts.
+async fn linked_vault<P: Provider + Clone>(
+    provider: &P,
+    kind: ContractKind,
+    address: Address,
+) -> Option<Address> {
+    let provider = provider.clone();
+    let result = match kind {
+        ContractKind::Manager => ManagerWithMerkleVerification::new(address, provider)
+            .vault()
+            .call()
+            .await
+            .map(|r| r._0),
+        ContractKind::Teller => TellerWithMultiAssetSupport::new(address, provider)
+            .vault()
+            .call()
+            .await
+            .map(|r| r._0),
+        ContractKind::Accountant => AccountantWithRateProviders::new(address, provider)
+            .vault()
+            .call()
+            .await
+            .map(|r| r._0),
+        ContractKind::Queue => BoringOnChainQueue::new(address, provider)
+            .boringVault()
+            .call()
+            .await
+            .map(|r| r._0),
+    };
+    result.ok()
+}
+
+// Safe among the proposers of a timelock, found through its RoleGranted events.
+async fn timelock_multisig<P: Provider + Clone>(
+    provider: &P,
+    timelock: Address,
+    from_block: u64,
+) -> Result<Option<Address>> {
+    let proposer_role = Timelock::new(timelock, provider.clone())
+        .PROPOSER_ROLE()
+        .call()
+        .await?
+        ._0;
+    let logs = get_event_logs(
+        provider,
+        timelock,
+        vec![Timelock::RoleGranted::SIGNATURE_HASH],
+        from_block,
+    )
+    .await?;
+
+    for log in logs {
+        let Ok(event) = log.log_decode::<Timelock::RoleGranted>() else {
+            continue;
+        };
+        let account = event.inner.data.account;
+        if event.inner.data.role == proposer_role
+            && code_has_function(&provider.get_code_at(account).await?, "getOwners()")
+        {
+            return Ok(Some(account));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Finds the contracts of a product from its vault. The roles authority and owner are read from
+/// the vault, the teller from its before transfer hook and the accountant from the teller. Other
+/// contracts are found among the users and targets of the roles authority events emitted since
+/// `from_block`, and kept if they point back at the vault. If the vault is owned by a timelock,
+/// the multisig is the Safe among its proposers.
+pub async fn discover_product(
+    cw: &ConfigWrapper,
+    network_id: u32,
+    vault: &str,
+    from_block: u64,
+) -> Result<DiscoveredProduct> {
+    let vault = vault.parse::<Address>()?;
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+    if provider.get_code_at(vault).await?.is_empty() {
+        return Err(eyre!("Vault {} has no code on {}", vault, network_id));
+    }
+
+    let mut product = DiscoveredProduct {
+        vault,
+        ..Default::default()
+    };
+
+    let auth = Auth::new(vault, provider.clone());
+    let owner = auth.owner().call().await?._0;
+    let roles_authority = auth.authority().call().await?._0;
+
+    // Candidates are checked in order, so getter based ones win over event based ones.
+    let mut candidates = Vec::new();
+    if let Ok(hook) = BoringVault::new(vault, provider.clone())
+        .hook()
+        .call()
+        .await
+    {
+        candidates.push(hook._0);
+    }
+    if roles_authority != Address::ZERO {
+        product.roles_authority = Some(roles_authority);
+        candidates.extend(roles_authority_addresses(&provider, roles_authority, from_block).await?);
+    }
+
+    let mut index = 0;
+    while index < candidates.len() {
+        let candidate = candidates[index];
+        index += 1;
+        if candidate == Address::ZERO || candidate == vault {
+            continue;
+        }
+        let Some(kind) = classify(&provider.get_code_at(candidate).await?) else {
+            continue;
+        };
+        let slot = match kind {
+            ContractKind::Manager => &mut product.manager,
+            ContractKind::Teller => &mut product.teller,
+            ContractKind::Accountant => &mut product.accountant,
+            ContractKind::Queue => &mut product.queue,
+        };
+        if slot.is_some() || linked_vault(&provider, kind, candidate).await != Some(vault) {
+            continue;
+        }
+        *slot = Some(candidate);
+
+        // The accountant of the teller is checked next.
+        if kind == ContractKind::Teller
+            && let Ok(accountant) = TellerWithMultiAssetSupport::new(candidate, provider.clone())
+                .accountant()
+                .call()
+                .await
+        {
+            candidates.insert(index, accountant._0);
+        }
+    }
+
+    let owner_code = provider.get_code_at(owner).await?;
+    if code_has_function(&owner_code, "getMinDelay()") {
+        product.timelock = Some(owner);
+        product.multisig = timelock_multisig(&provider, owner, from_block).await?;
+    } else if code_has_function(&owner_code, "getOwners()") {
+        product.multisig = Some(owner);
+    }
+
+    Ok(product)
+}
@@ -1 +1,3 @@
+pub mod discover;
+pub mod probe;
 pub mod simulate;
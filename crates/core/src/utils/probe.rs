@@ -0,0 +1,229 @@
+use crate::bindings::{boring_queue::BoringOnChainQueue, multisig::GnosisSafe};
+use crate::types::{
+    config_wrapper::ConfigWrapper,
+    contract_versions::{AccountantVersion, ContractVersions, QueueVersion, TellerVersion},
+};
+use alloy::primitives::{Address, keccak256};
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::TransactionRequest;
+use alloy::sol_types::SolCall;
+use eyre::{Result, eyre};
+
+// Returns true if the runtime code pushes the selector of `signature`, which is how the solidity
+// function dispatcher matches selectors.
+pub(crate) fn code_has_function(code: &[u8], signature: &str) -> bool {
+    let selector = &keccak256(signature.as_bytes())[..4];
+    // Selectors are pushed with the smallest PUSH1 (0x60) to PUSH4 (0x63) holding them, so leading
+    // zero bytes are dropped.
+    let zeros = selector.iter().take_while(|b| **b == 0).count().min(3);
+    let selector = &selector[zeros..];
+    let push = 0x5f + selector.len() as u8;
+    code.windows(selector.len() + 1)
+        .any(|w| w[0] == push && &w[1..] == selector)
+}
+
+pub async fn probe_teller_version<P: Provider>(
+    provider: &P,
+    teller: Address,
+) -> Result<TellerVersion> {
+    let code = provider.get_code_at(teller).await?;
+    if code_has_function(&code, "updateAssetData(address,bool,bool,uint16)") {
+        Ok(TellerVersion::V2)
+    } else if code_has_function(&code, "addAsset(address)") {
+        Ok(TellerVersion::V1)
+    } else {
+        Err(eyre!("Could not detect teller version of {}", teller))
+    }
+}
+
+pub async fn probe_accountant_version<P: Provider>(
+    provider: &P,
+    accountant: Address,
+) -> Result<AccountantVersion> {
+    let code = provider.get_code_at(accountant).await?;
+    if code_has_function(&code, "updatePlatformFee(uint16)") {
+        Ok(AccountantVersion::V2)
+    } else if code_has_function(&code, "updateManagementFee(uint16)") {
+        Ok(AccountantVersion::V1)
+    } else {
+        Err(eyre!(
+            "Could not detect accountant version of {}",
+            accountant
+        ))
+    }
+}
+
+pub async fn probe_queue_version<P: Provider>(
+    provider: &P,
+    queue: Address,
+) -> Result<QueueVersion> {
+    // Both versions share the withdrawAssets selector, but V2 returns an extra withdraw capacity
+    // word, so look at the size of the returned data.
+    let input = BoringOnChainQueue::withdrawAssetsCall::new((Address::ZERO,)).abi_encode();
+    let request = TransactionRequest::default().to(queue).input(input.into());
+    let output = provider.call(&request).await?;
+    match output.len() / 32 {
+        6 => Ok(QueueVersion::V1),
+        7 => Ok(QueueVersion::V2),
+        _ => Err(eyre!("Could not detect queue version of {}", queue)),
+    }
+}
+
+// Errors if `addr` has no code, used before pointing a contract at a new hook or authority.
+pub async fn ensure_has_code<P: Provider>(provider: &P, addr: Address, name: &str) -> Result<()> {
+    if provider.get_code_at(addr).await?.is_empty() {
+        return Err(eyre!("New {} {} has no code", name, addr));
+    }
+    Ok(())
+}
+
+// Errors if `addr` is not a Safe with owners and a threshold. Safes are proxies, so they are
+// called rather than probed through their bytecode.
+pub async fn ensure_is_safe<P: Provider + Clone>(
+    provider: &P,
+    addr: Address,
+    name: &str,
+) -> Result<()> {
+    ensure_has_code(provider, addr, name).await?;
+    let safe = GnosisSafe::new(addr, provider.clone());
+    let owners = safe.getOwners().call().await.map(|r| r.owners);
+    let threshold = safe.getThreshold().call().await.map(|r| r.threshold);
+    match (owners, threshold) {
+        (Ok(owners), Ok(threshold)) if !owners.is_empty() && !threshold.is_zero() => Ok(()),
+        _ => Err(eyre!(
+            "New {} {} is not a Safe, getOwners() and getThreshold() must return its owners",
+            name,
+            addr
+        )),
+    }
+}
+
+// Reads an address from the product config, returning None if it is missing or not an address.
+fn configured_address(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    key: &str,
+) -> Option<Address> {
+    cw.get_product_config_value(product, network_id, key)
+        .ok()
+        .and_then(|addr| addr.parse::<Address>().ok())
+        .filter(|addr| *addr != Address::ZERO)
+}
+
+// Detects the interface versions of every configured product contract on a network.
+pub async fn probe_contract_versions(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+) -> Result<ContractVersions> {
+    let provider = ProviderBuilder::new()
+        .on_builtin(&cw.get_rpc_url(network_id)?)
+        .await?;
+
+    let mut versions = ContractVersions::default();
+    if let Some(teller) = configured_address(cw, product, network_id, "teller_address") {
+        versions.teller = Some(probe_teller_version(&provider, teller).await?);
+    }
+    if let Some(accountant) = configured_address(cw, product, network_id, "accountant_address") {
+        versions.accountant = Some(probe_accountant_version(&provider, accountant).await?);
+    }
+    if let Some(queue) = configured_address(cw, product, network_id, "queue_address") {
+        versions.queue = Some(probe_queue_version(&provider, queue).await?);
+    }
+
+    Ok(versions)
+}
+
+// Teller version from config, probed on chain if it has not been recorded yet.
+pub async fn resolve_teller_version<P: Provider>(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    provider: &P,
+    teller: Address,
+) -> Result<TellerVersion> {
+    match cw.get_product_versions(product, network_id)?.teller {
+        Some(version) => Ok(version),
+        None => probe_teller_version(provider, teller).await,
+    }
+}
+
+// Accountant version from config, probed on chain if it has not been recorded yet.
+pub async fn resolve_accountant_version<P: Provider>(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    provider: &P,
+    accountant: Address,
+) -> Result<AccountantVersion> {
+    match cw.get_product_versions(product, network_id)?.accountant {
+        Some(version) => Ok(version),
+        None => probe_accountant_version(provider, accountant).await,
+    }
+}
+
+// Queue version from config, probed on chain if it has not been recorded yet.
+pub async fn resolve_queue_version<P: Provider>(
+    cw: &ConfigWrapper,
+    product: &str,
+    network_id: u32,
+    provider: &P,
+    queue: Address,
+) -> Result<QueueVersion> {
+    match cw.get_product_versions(product, network_id)?.queue {
+        Some(version) => Ok(version),
+        None => probe_queue_version(provider, queue).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Dispatcher like code comparing the calldata selector with `selector`, pushed with `push`.
+    fn dispatcher(push: u8, selector: &[u8]) -> Vec<u8> {
+        let mut code = vec![0x80];
+        code.push(push);
+        code.extend_from_slice(selector);
+        code.extend_from_slice(&[0x14, 0x61, 0x00, 0x2a, 0x57]);
+        code
+    }
+
+    // First `f<n>()` signature whose selector starts with `zeros` zero bytes.
+    fn signature_with_leading_zeros(zeros: usize) -> String {
+        (0u64..)
+            .map(|n| format!("f{}()", n))
+            .find(|signature| {
+                let selector = &keccak256(signature.as_bytes())[..4];
+                selector[..zeros].iter().all(|b| *b == 0) && selector[zeros] != 0
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn finds_selectors_pushed_with_push4() {
+        let selector = &keccak256("addAsset(address)".as_bytes())[..4];
+        let code = dispatcher(0x63, selector);
+        assert!(code_has_function(&code, "addAsset(address)"));
+        assert!(!code_has_function(&code, "getRate()"));
+    }
+
+    #[test]
+    fn finds_selectors_with_leading_zeros_pushed_with_shorter_pushes() {
+        let signature = signature_with_leading_zeros(1);
+        let selector = &keccak256(signature.as_bytes())[1..4];
+        assert!(code_has_function(&dispatcher(0x62, selector), &signature));
+        // A PUSH4 of the selector bytes after the zero is not the same selector.
+        assert!(!code_has_function(&dispatcher(0x63, selector), &signature));
+    }
+
+    #[test]
+    fn ignores_selectors_not_pushed() {
+        let selector = &keccak256("addAsset(address)".as_bytes())[..4];
+        let mut code = selector.to_vec();
+        code.insert(0, 0x00);
+        assert!(!code_has_function(&code, "addAsset(address)"));
+        assert!(!code_has_function(&[], "addAsset(address)"));
+    }
+}
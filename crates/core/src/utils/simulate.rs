@@ -1,36 +1,40 @@
 use crate::bindings::multisig::GnosisSafe::{self, GnosisSafeInstance};
-use crate::types::{config_wrapper::ConfigWrapper, simulation_config::SimulationConfig};
-use alloy::primitives::{Address, Bytes, U256};
+use crate::types::{
+    config_wrapper::ConfigWrapper, simulation_config::SimulationConfig, transaction::Transaction,
+};
+use alloy::primitives::{Address, Bytes, U256, address};
 use alloy::{providers::ProviderBuilder, sol_types::SolCall};
 use dotenv::dotenv;
 use eyre::Result;
-use hex;
 use reqwest::Client;
 use serde_json::{Value, json};
 use std::env;
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-pub async fn generate_safe_hash_and_return_params(
-    safe: &GnosisSafeInstance<
-        (),
-        alloy::providers::fillers::FillProvider<
+/// Address simulations execute Safe txs from, enabled as a module through a storage override.
+pub const SIMULATION_MODULE: Address = address!("0xe2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2");
+
+/// Provider type returned by `ProviderBuilder::new().on_builtin(..)`.
+pub type DefaultProvider = alloy::providers::fillers::FillProvider<
+    alloy::providers::fillers::JoinFill<
+        alloy::providers::Identity,
+        alloy::providers::fillers::JoinFill<
+            alloy::providers::fillers::GasFiller,
             alloy::providers::fillers::JoinFill<
-                alloy::providers::Identity,
+                alloy::providers::fillers::BlobGasFiller,
                 alloy::providers::fillers::JoinFill<
-                    alloy::providers::fillers::GasFiller,
-                    alloy::providers::fillers::JoinFill<
-                        alloy::providers::fillers::BlobGasFiller,
-                        alloy::providers::fillers::JoinFill<
-                            alloy::providers::fillers::NonceFiller,
-                            alloy::providers::fillers::ChainIdFiller,
-                        >,
-                    >,
+                    alloy::providers::fillers::NonceFiller,
+                    alloy::providers::fillers::ChainIdFiller,
                 >,
             >,
-            alloy::providers::RootProvider,
         >,
     >,
+    alloy::providers::RootProvider,
+>;
+
+pub async fn generate_safe_hash_and_return_params(
+    safe: &GnosisSafeInstance<(), DefaultProvider>,
     config: &SimulationConfig,
 ) -> Result<(String, Address, U256, Bytes, u8)> {
     let safe_tx_gas = U256::ZERO;
@@ -110,7 +114,7 @@ pub async fn simulate_admin_tx_and_generate_safe_hash(
     let input_hex = hex::encode(input);
 
     let response = client
-        .post(&format!(
+        .post(format!(
             "https://api.tenderly.co/api/v1/account/{}/project/{}/simulate",
             account_slug, project_slug
         ))
@@ -151,6 +155,21 @@ pub async fn simulate_timelock_admin_txs_and_generate_safe_hashes(
     propose_tx_path: String,
     execute_tx_path: String,
 ) -> Result<(String, String, String)> {
+    let (vnet_url, mut safe_hashes) =
+        simulate_admin_txs_in_sequence(&[propose_tx_path, execute_tx_path], Some(1)).await?;
+    let execute_safe_hash_hex = safe_hashes.pop().unwrap_or_default();
+    let propose_safe_hash_hex = safe_hashes.pop().unwrap_or_default();
+
+    Ok((vnet_url, propose_safe_hash_hex, execute_safe_hash_hex))
+}
+
+// Simulates admin txs one after another on a vnet, so each tx sees the state left by the
+// previous ones. Txs from `execute_from` onwards are executed 30 days later, to get past any
+// timelock delay.
+pub async fn simulate_admin_txs_in_sequence(
+    tx_paths: &[String],
+    execute_from: Option<usize>,
+) -> Result<(String, Vec<String>)> {
     dotenv().ok();
 
     let cw = ConfigWrapper::from_file(None)?;
@@ -159,16 +178,24 @@ pub async fn simulate_timelock_admin_txs_and_generate_safe_hashes(
     let account_slug = env::var("TENDERLY_ACCOUNT_SLUG")?;
     let project_slug = env::var("TENDERLY_PROJECT_SLUG")?;
 
-    let propose_config = SimulationConfig::from_file(&propose_tx_path)?;
-    let execute_config = SimulationConfig::from_file(&execute_tx_path)?;
-
-    // Validate matching fields between propose and execute configs
-    if propose_config.network_id != execute_config.network_id {
-        return Err(eyre::eyre!(
-            "Network IDs do not match: propose={}, execute={}",
-            propose_config.network_id,
-            execute_config.network_id
-        ));
+    let configs = tx_paths
+        .iter()
+        .map(|path| SimulationConfig::from_file(path))
+        .collect::<Result<Vec<_>>>()?;
+    let network_id = configs
+        .first()
+        .ok_or_else(|| eyre::eyre!("No transactions to simulate"))?
+        .network_id;
+
+    // Validate matching fields between all configs
+    for config in &configs {
+        if config.network_id != network_id {
+            return Err(eyre::eyre!(
+                "Network IDs do not match: first={}, found={}",
+                network_id,
+                config.network_id
+            ));
+        }
     }
 
     let from_address = "0xe2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2e2";
@@ -177,7 +204,7 @@ pub async fn simulate_timelock_admin_txs_and_generate_safe_hashes(
     let client = Client::new();
 
     let create_vnet_response = client
-        .post(&format!(
+        .post(format!(
             "https://api.tenderly.co/api/v1/account/{}/project/{}/vnets",
             account_slug, project_slug
         ))
@@ -185,11 +212,11 @@ pub async fn simulate_timelock_admin_txs_and_generate_safe_hashes(
         .json(&json!({
             "slug": vnet_slug,
             "fork_config": {
-                "network_id": propose_config.network_id
+                "network_id": network_id
             },
             "virtual_network_config": {
                 "chain_config": {
-                    "chain_id": propose_config.network_id
+                    "chain_id": network_id
                 }
             }
         }))
@@ -208,98 +235,220 @@ pub async fn simulate_timelock_admin_txs_and_generate_safe_hashes(
         .unwrap()
         .as_secs();
 
-    // Generate Safe Hash
-    let rpc_url = cw.get_rpc_url(propose_config.network_id)?;
+    let rpc_url = cw.get_rpc_url(network_id)?;
     let provider = ProviderBuilder::new().on_builtin(&rpc_url).await?;
-    let safe_address = propose_config.multisig();
-    let safe = GnosisSafe::new(safe_address, provider);
 
-    let (propose_safe_hash_hex, to_address, value, data, operation) =
-        generate_safe_hash_and_return_params(&safe, &propose_config).await?;
+    let mut safe_hashes = Vec::with_capacity(configs.len());
+    for (i, config) in configs.iter().enumerate() {
+        // Generate Safe Hash
+        let safe = GnosisSafe::new(config.multisig(), provider.clone());
+        let (safe_hash_hex, to_address, value, data, operation) =
+            generate_safe_hash_and_return_params(&safe, config).await?;
+        safe_hashes.push(safe_hash_hex);
+
+        // Build input.
+        let input =
+            GnosisSafe::execTransactionFromModuleCall::new((to_address, value, data, operation))
+                .abi_encode();
+
+        let input_hex = hex::encode(input);
+
+        let time = match execute_from {
+            Some(index) if i >= index => timestamp + 30 * 86_400 + i as u64,
+            _ => timestamp + 1 + i as u64,
+        };
+
+        let _response = client
+            .post(format!(
+                "https://api.tenderly.co/api/v1/account/{}/project/{}/vnets/{}/transactions",
+                account_slug, project_slug, vnet_id
+            ))
+            .header("X-Access-Key", api_key.clone())
+            .json(&json!({
+                "callArgs": {
+                    "from": from_address,
+                    "to": config.multisig,
+                    "gas": format!("0x{:x}", 10_000_000),
+                    "gasPrice": "0x0",
+                    "value": "0x0",
+                    "data": format!("0x{}", input_hex)
+                },
+                "blockOverrides": {
+                  "time": format!("0x{:x}", time)
+                },
+                "stateOverrides": {
+                    config.multisig.clone(): {
+                        "stateDiff": {
+                            "0xd71a90a935e1abe19645d4f9630a0044413a815e634f2ca5c4b4b04becfec14c": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                        }
+                    }
+                }
+            }))
+            .send()
+            .await?;
+    }
 
-    // Build input.
-    let input =
-        GnosisSafe::execTransactionFromModuleCall::new((to_address, value, data, operation))
-            .abi_encode();
+    // NOTE for debugging
+    // let response_json = response.json::<serde_json::Value>().await?;
+    // fs::write("tmp.json", serde_json::to_string_pretty(&response_json)?)?;
 
-    let input_hex = hex::encode(input);
+    let vnet_url = format!(
+        "https://dashboard.tenderly.co/{}/{}/testnet/{}",
+        account_slug, project_slug, vnet_id
+    );
 
-    client
-        .post(&format!(
-            "https://api.tenderly.co/api/v1/account/{}/project/{}/vnets/{}/transactions",
-            account_slug, project_slug, vnet_id
-        ))
-        .header("X-Access-Key", api_key.clone())
-        .json(&json!({
-            "callArgs": {
-                "from": from_address,
-                "to": propose_config.multisig,
-                "gas": format!("0x{:x}", 10_000_000),
-                "gasPrice": "0x0",
-                "value": "0x0",
-                "data": format!("0x{}", input_hex)
-            },
-            "blockOverrides": {
-              "time": format!("0x{:x}", timestamp + 1)
-            },
-            "stateOverrides": {
-                propose_config.multisig.clone(): {
-                    "stateDiff": {
-                        "0xd71a90a935e1abe19645d4f9630a0044413a815e634f2ca5c4b4b04becfec14c": "0x0000000000000000000000000000000000000000000000000000000000000001"
-                    }
+    Ok((vnet_url, safe_hashes))
+}
+
+// Bundle simulation entry executing `tx` from `safe` through execTransactionFromModule, with the
+// simulation address enabled as a module through a storage override.
+fn module_exec_simulation(network_id: u32, safe: Address, tx: &Transaction, time: u64) -> Value {
+    let from_address = SIMULATION_MODULE.to_string();
+    let safe = safe.to_string();
+    let input = GnosisSafe::execTransactionFromModuleCall::new((
+        tx.to,
+        tx.value,
+        tx.data.clone(),
+        tx.operation.as_u8(),
+    ))
+    .abi_encode();
+    json!({
+        "network_id": network_id,
+        "from": from_address,
+        "to": safe,
+        "input": hex::encode(input),
+        "gas": 30_000_000,
+        "estimate_gas": true,
+        "simulation_type": "quick",
+        "save": false,
+        "block_header": {
+            "timestamp": format!("0x{:x}", time)
+        },
+        "state_objects": {
+            safe.clone(): {
+                "storage": {
+                    "0xd71a90a935e1abe19645d4f9630a0044413a815e634f2ca5c4b4b04becfec14c": "0x0000000000000000000000000000000000000000000000000000000000000001"
                 }
             }
-        }))
+        }
+    })
+}
+
+// Runs simulations in order in a single Tenderly bundle, returning the transaction result of
+// each simulation.
+async fn simulate_bundle(simulations: Vec<Value>) -> Result<Vec<Value>> {
+    dotenv().ok();
+
+    let api_key = env::var("TENDERLY_ACCESS_KEY")?;
+    let account_slug = env::var("TENDERLY_ACCOUNT_SLUG")?;
+    let project_slug = env::var("TENDERLY_PROJECT_SLUG")?;
+
+    let count = simulations.len();
+    let client = Client::new();
+    let response = client
+        .post(format!(
+            "https://api.tenderly.co/api/v1/account/{}/project/{}/simulate-bundle",
+            account_slug, project_slug
+        ))
+        .header("X-Access-Key", api_key)
+        .json(&json!({ "simulations": simulations }))
         .send()
         .await?;
 
-    let (execute_safe_hash_hex, to_address, value, data, operation) =
-        generate_safe_hash_and_return_params(&safe, &execute_config).await?;
+    let bundle_result = response.json::<Value>().await?;
+    let results = bundle_result
+        .get("simulation_results")
+        .and_then(|r| r.as_array())
+        .ok_or_else(|| eyre::eyre!("Bundle simulation failed: {}", bundle_result))?;
 
-    // Build input.
-    let input =
-        GnosisSafe::execTransactionFromModuleCall::new((to_address, value, data, operation))
-            .abi_encode();
+    if results.len() != count {
+        return Err(eyre::eyre!(
+            "Bundle simulation returned {} results for {} simulations",
+            results.len(),
+            count
+        ));
+    }
 
-    let input_hex = hex::encode(input);
+    Ok(results
+        .iter()
+        .map(|r| r.get("transaction").cloned().unwrap_or(Value::Null))
+        .collect())
+}
 
-    let _response = client
-        .post(&format!(
-            "https://api.tenderly.co/api/v1/account/{}/project/{}/vnets/{}/transactions",
-            account_slug, project_slug, vnet_id
-        ))
-        .header("X-Access-Key", api_key.clone())
-        .json(&json!({
-            "callArgs": {
-                "from": from_address,
-                "to": execute_config.multisig,
-                "gas": format!("0x{:x}", 10_000_000),
-                "gasPrice": "0x0",
-                "value": "0x0",
-                "data": format!("0x{}", input_hex)
-            },
-            "blockOverrides": {
-              "time": format!("0x{:x}", timestamp + 30 * 86_400)
-            },
-            "stateOverrides": {
-                execute_config.multisig.clone(): {
-                    "stateDiff": {
-                        "0xd71a90a935e1abe19645d4f9630a0044413a815e634f2ca5c4b4b04becfec14c": "0x0000000000000000000000000000000000000000000000000000000000000001"
-                    }
-                }
+// Timestamp every tx is simulated at, txs from `execute_from` onwards are simulated 30 days later
+// to get past any timelock delay.
+fn bundle_timestamps(count: usize, execute_from: Option<usize>) -> Vec<u64> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    (0..count)
+        .map(|i| match execute_from {
+            Some(index) if i >= index => timestamp + 30 * 86_400 + i as u64,
+            _ => timestamp + 1 + i as u64,
+        })
+        .collect()
+}
+
+// Estimates the gas used by each tx when executed by the multisig, in order, using a bundle
+// simulation so later txs see the state changes of earlier ones. Txs from `execute_from` onwards
+// are simulated 30 days later, to get past any timelock delay.
+pub async fn estimate_gas_for_txs(
+    network_id: u32,
+    multisig: Address,
+    txs: &[Transaction],
+    execute_from: Option<usize>,
+) -> Result<Vec<u64>> {
+    let simulations = txs
+        .iter()
+        .zip(bundle_timestamps(txs.len(), execute_from))
+        .map(|(tx, time)| module_exec_simulation(network_id, multisig, tx, time))
+        .collect();
+
+    simulate_bundle(simulations)
+        .await?
+        .iter()
+        .enumerate()
+        .map(|(i, transaction)| {
+            if !transaction["status"].as_bool().unwrap_or(false) {
+                return Err(eyre::eyre!("Gas estimation for tx {} reverted", i));
             }
-        }))
-        .send()
-        .await?;
+            transaction["gas_used"]
+                .as_u64()
+                .ok_or_else(|| eyre::eyre!("gas_used not found for tx {}", i))
+        })
+        .collect()
+}
 
-    // NOTE for debugging
-    // let response_json = response.json::<serde_json::Value>().await?;
-    // fs::write("tmp.json", serde_json::to_string_pretty(&response_json)?)?;
+// Executes the txs from the multisig like `estimate_gas_for_txs`, then every call from `new_safe`,
+// returning whether each call succeeded. Used to check that a new Safe can still administer the
+// product after the txs are executed.
+pub async fn simulate_calls_from_new_safe(
+    network_id: u32,
+    multisig: Address,
+    txs: &[Transaction],
+    execute_from: Option<usize>,
+    new_safe: Address,
+    calls: &[Transaction],
+) -> Result<Vec<bool>> {
+    let times = bundle_timestamps(txs.len() + calls.len(), execute_from);
+    let mut simulations = Vec::with_capacity(times.len());
+    for (tx, time) in txs.iter().zip(&times) {
+        simulations.push(module_exec_simulation(network_id, multisig, tx, *time));
+    }
+    for (call, time) in calls.iter().zip(&times[txs.len()..]) {
+        simulations.push(module_exec_simulation(network_id, new_safe, call, *time));
+    }
 
-    let vnet_url = format!(
-        "https://dashboard.tenderly.co/{}/{}/testnet/{}",
-        account_slug, project_slug, vnet_id
-    );
+    let results = simulate_bundle(simulations).await?;
+    for (i, transaction) in results[..txs.len()].iter().enumerate() {
+        if !transaction["status"].as_bool().unwrap_or(false) {
+            return Err(eyre::eyre!("Simulation of tx {} reverted", i));
+        }
+    }
 
-    Ok((vnet_url, propose_safe_hash_hex, execute_safe_hash_hex))
+    Ok(results[txs.len()..]
+        .iter()
+        .map(|transaction| transaction["status"].as_bool().unwrap_or(false))
+        .collect())
 }